@@ -11,6 +11,29 @@ declare_id!("6W1NLpkZvfu6y44nmCtQBLUEjGZQoCt6zQ9MouStHrFK");
 
 pub const ADMIN_PUBKEY: &str = "5DcirLSutTThvZu9AJK9yGWXqs4HHumRvrtzZQggb7dW";
 
+pub const MAX_STAT_COMPONENTS: usize = 4;
+// Component weights are expressed in hundredths (100 = 1.0x)
+pub const STAT_WEIGHT_SCALE: u64 = 100;
+pub const MAX_STAT_TYPES: usize = 16;
+pub const MAX_STAT_AMENDMENTS: usize = 8;
+// The last amendment slot is kept free for an upheld dispute
+pub const MAX_PROPOSER_AMENDMENTS: usize = MAX_STAT_AMENDMENTS - 1;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const DEFAULT_ROYALTY_BPS: u16 = 250;
+pub const MAX_COUNCIL_MEMBERS: usize = 10;
+pub const MAX_JURISDICTIONS: usize = 8;
+pub const PAUSE_BETTING: u8 = 1 << 0;
+pub const PAUSE_MARKETPLACE: u8 = 1 << 1;
+pub const PAUSE_CLAIMS: u8 = 1 << 2;
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+pub const SECONDS_PER_WEEK: i64 = 7 * SECONDS_PER_DAY;
+// Delay before a loosened responsible-gaming limit takes effect
+pub const LIMIT_INCREASE_COOLDOWN: i64 = SECONDS_PER_DAY;
+pub const SESSION_PLACE_BET: u8 = 1 << 0;
+pub const SESSION_SETTLE_CLAIM: u8 = 1 << 1;
+pub const SESSION_ALL: u8 = SESSION_PLACE_BET | SESSION_SETTLE_CLAIM;
+pub const PAUSE_ALL: u8 = PAUSE_BETTING | PAUSE_MARKETPLACE | PAUSE_CLAIMS;
+
 
 #[error_code]
 pub enum ErrorCode {
@@ -38,753 +61,4114 @@ pub enum ErrorCode {
     UnexpectedDelegate,
     #[msg("Escrow token account has an unexpected close authority.")]
     UnexpectedCloseAuthority,
+    #[msg("Composite stat components are invalid.")]
+    InvalidStatComponents,
+    #[msg("Stat components can only be changed before any bets are placed.")]
+    PoolAlreadyActive,
+    #[msg("This pool is a composite prop; publish per-component values instead.")]
+    CompositeResultRequired,
+    #[msg("This pool is not a composite prop.")]
+    NotCompositePool,
+    #[msg("Component values do not match the pool's stat components.")]
+    ComponentCountMismatch,
+    #[msg("Composite stat overflowed.")]
+    StatOverflow,
+    #[msg("Fixture does not match the bet pool.")]
+    FixtureMismatch,
+    #[msg("Fixture is not open for new pools or bets.")]
+    FixtureNotScheduled,
+    #[msg("Invalid fixture status transition.")]
+    InvalidFixtureStatus,
+    #[msg("Fixture was postponed; its pools are void.")]
+    FixturePostponed,
+    #[msg("Betting is closed for this pool.")]
+    BettingClosed,
+    #[msg("Player is not active.")]
+    PlayerInactive,
+    #[msg("Player does not match the bet pool.")]
+    PlayerMismatch,
+    #[msg("Names must be uppercase A-Z, 0-9 or '_' and zero padded.")]
+    NonCanonicalName,
+    #[msg("Stat type is not registered for this sport.")]
+    InvalidStatType,
+    #[msg("Stat type range is invalid.")]
+    InvalidStatRange,
+    #[msg("Sport has no room for more stat types.")]
+    SportStatTypesFull,
+    #[msg("Stat value is outside the plausible range for this stat type.")]
+    ImplausibleStatValue,
+    #[msg("Batch must contain at least one pool.")]
+    EmptyBatch,
+    #[msg("Remaining accounts do not match the batch entries.")]
+    BatchAccountsMismatch,
+    #[msg("Account does not match the expected PDA.")]
+    InvalidPda,
+    #[msg("Bet pool still has unsettled picks within the claim period.")]
+    PoolNotClosable,
+    #[msg("Treasury account does not match config.")]
+    InvalidTreasury,
+    #[msg("The claim deadline for this pool has passed.")]
+    ClaimDeadlinePassed,
+    #[msg("The claim deadline for this pool has not passed yet.")]
+    ClaimPeriodActive,
+    #[msg("Destination does not match the configured unclaimed destination.")]
+    InvalidUnclaimedDestination,
+    #[msg("Claim period must be positive.")]
+    InvalidClaimPeriod,
+    #[msg("Resolution timeout must be positive.")]
+    InvalidResolutionTimeout,
+    #[msg("The resolution timeout has passed; this pool can only be refunded.")]
+    ResolutionTimedOut,
+    #[msg("The resolution timeout has not passed yet.")]
+    ResolutionPending,
+    #[msg("Holder token account does not hold this pick.")]
+    NotPickHolder,
+    #[msg("Bet pool must be locked before a result can be proposed.")]
+    PoolNotLocked,
+    #[msg("Bet pool has no proposed result.")]
+    PoolNotProposed,
+    #[msg("The dispute window is still open.")]
+    DisputeWindowOpen,
+    #[msg("Dispute window must not be negative.")]
+    InvalidDisputeWindow,
+    #[msg("The dispute window has closed.")]
+    DisputeWindowClosed,
+    #[msg("A dispute is already open for this pool.")]
+    DisputeOpen,
+    #[msg("Disputed value matches the proposed result.")]
+    DisputeMatchesResult,
+    #[msg("The stat-correction window has closed.")]
+    CorrectionWindowClosed,
+    #[msg("The stat-correction window is still open.")]
+    CorrectionWindowOpen,
+    #[msg("Correction window must not be negative.")]
+    InvalidCorrectionWindow,
+    #[msg("Amendment history is full for this pool.")]
+    TooManyAmendments,
+    #[msg("Crank batch must contain at least one pick.")]
+    EmptyCrank,
+    #[msg("Payout would overdraw the bet vault.")]
+    VaultOverdrawn,
+    #[msg("Vault does not belong to this pool.")]
+    InvalidVaultOwner,
+    #[msg("Signer does not hold the role required for this instruction.")]
+    MissingRole,
+    #[msg("Royalty must be at most 10000 basis points.")]
+    InvalidRoyalty,
+    #[msg("Council needs 1..=10 unique members and a threshold between 1 and the member count.")]
+    InvalidCouncil,
+    #[msg("Signer is not a council member.")]
+    NotCouncilMember,
+    #[msg("This member already approved the proposal.")]
+    AlreadyApproved,
+    #[msg("Proposal has already been executed.")]
+    ProposalExecuted,
+    #[msg("Proposal has not reached the council threshold.")]
+    InsufficientApprovals,
+    #[msg("Accounts supplied do not match the proposed action.")]
+    ActionAccountsMismatch,
+    #[msg("This action is paused.")]
+    Paused,
+    #[msg("Unknown pause flags.")]
+    InvalidPauseFlags,
+    #[msg("The account for this pause scope was not supplied.")]
+    PauseTargetMissing,
+    #[msg("User profile does not belong to this wallet.")]
+    ProfileMismatch,
+    #[msg("This wallet is self-excluded from betting.")]
+    SelfExcluded,
+    #[msg("Daily deposit limit reached.")]
+    DailyDepositLimitReached,
+    #[msg("Weekly deposit limit reached.")]
+    WeeklyDepositLimitReached,
+    #[msg("Weekly loss limit reached.")]
+    LossLimitReached,
+    #[msg("Self-exclusion period must be positive.")]
+    InvalidExclusionPeriod,
+    #[msg("This pool requires a compliance attestation.")]
+    AttestationRequired,
+    #[msg("Compliance attestation has expired.")]
+    AttestationExpired,
+    #[msg("Wallet's jurisdiction is not allowed for this pool.")]
+    JurisdictionNotAllowed,
+    #[msg("Too many allowed jurisdictions.")]
+    TooManyJurisdictions,
+    #[msg("This wallet is blocked.")]
+    WalletBlocked,
+    #[msg("Block account does not match the wallet.")]
+    InvalidBlockAccount,
+    #[msg("Internal balance is too low.")]
+    InsufficientBalance,
+    #[msg("Balance account does not belong to this wallet.")]
+    BalanceMismatch,
+    #[msg("Signer is neither the owner nor a valid session key for the owner.")]
+    InvalidSession,
+    #[msg("Session has expired.")]
+    SessionExpired,
+    #[msg("Session is not allowed to call this instruction.")]
+    SessionInstructionNotAllowed,
+    #[msg("Session spend cap exceeded.")]
+    SessionSpendCapExceeded,
+    #[msg("Session expiry and instruction set must be valid.")]
+    InvalidSessionParams,
+    #[msg("Session-signed bets must be funded from the internal balance.")]
+    BalanceRequired,
+    #[msg("Unclaimed winnings must be swept before the pool can be closed.")]
+    UnclaimedNotSwept,
+    #[msg("Council membership changed since this proposal was opened.")]
+    ProposalStale,
+    #[msg("Buyer and seller must be different wallets.")]
+    SelfPurchase,
+    #[msg("Buyer and seller balances must be different accounts.")]
+    DuplicateBalance,
+    #[msg("This pick is already listed for sale.")]
+    AlreadyListed,
+    #[msg("Listed picks must be reclaimed before they can be refunded.")]
+    PickListed,
+    #[msg("Dispute timeout must be positive.")]
+    InvalidDisputeTimeout,
+    #[msg("Dispute bond must be positive.")]
+    InvalidDisputeBond,
+    #[msg("The dispute is still awaiting arbitration.")]
+    DisputePending,
+    #[msg("Composite results are corrected through the council, not disputes.")]
+    CompositeDisputeUnsupported,
+    #[msg("Withdraw the pool's fees through the council before closing it.")]
+    FeesNotWithdrawn,
 }
 
+fn require_admin(authority: &Pubkey) -> Result<()> {
+    let admin_pk = Pubkey::from_str(ADMIN_PUBKEY).unwrap();
+    require!(*authority == admin_pk, ErrorCode::Unauthorized);
+    Ok(())
+}
 
+// The admin implicitly holds every role; anyone else needs a matching grant
+fn require_role(authority: &Pubkey, grant: Option<&Account<RoleGrant>>, role: Role) -> Result<()> {
+    if require_admin(authority).is_ok() {
+        return Ok(());
+    }
+    let grant = grant.ok_or(ErrorCode::MissingRole)?;
+    require!(
+        grant.grantee == *authority && grant.role == role,
+        ErrorCode::MissingRole
+    );
+    Ok(())
+}
 
+// A flag raised at any level (global, sport or pool) blocks the action
+fn require_not_paused(flag: u8, config: &Config, sport: &Sport, bet_pool: &BetPool) -> Result<()> {
+    let paused = config.paused_flags | sport.paused_flags | bet_pool.paused_flags;
+    require!(paused & flag == 0, ErrorCode::Paused);
+    Ok(())
+}
 
-#[program]
-pub mod fantasy_sports {
-    use super::*;
-
-pub fn initialize_bet_pool(
-    ctx: Context<InitializeBetPool>,
-    fixture_id: u64,
-    player_id: Pubkey,
-    stat_name: [u8; 32],
-    stat_line: u32,
-    sport_name: [u8; 32],
-    betting_deadline: i64,
+// Takes funds from the wallet's internal balance when supplied, otherwise transfers from the wallet
+fn collect_from<'info>(
+    wallet: &AccountInfo<'info>,
+    balance: Option<&mut Account<'info, Balance>>,
+    to: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
 ) -> Result<()> {
-    msg!("🧪 fixture_id: {}", fixture_id);
-    msg!("🧪 stat_line: {}", stat_line);
-    msg!("🧪 player_id: {}", player_id);
+    match balance {
+        Some(balance) => {
+            require_keys_eq!(balance.owner, wallet.key(), ErrorCode::BalanceMismatch);
+            require!(balance.amount >= amount, ErrorCode::InsufficientBalance);
+            **balance.to_account_info().try_borrow_mut_lamports()? -= amount;
+            **to.try_borrow_mut_lamports()? += amount;
+            balance.amount -= amount;
+        }
+        None => {
+            require!(wallet.is_signer, ErrorCode::BalanceRequired);
+            invoke(
+                &system_instruction::transfer(&wallet.key(), &to.key(), amount),
+                &[wallet.clone(), to.clone(), system_program.clone()],
+            )?;
+        }
+    }
+    Ok(())
+}
 
-    require!(fixture_id > 0, ErrorCode::InvalidFixture);
-    require!(stat_line > 0, ErrorCode::InvalidStatLine);
+// The owner may always act for itself; anyone else needs a live session for the owner
+fn authorize_session(
+    owner: &Pubkey,
+    authority: &Pubkey,
+    session: Option<&mut Account<SessionToken>>,
+    instruction: u8,
+    spend: u64,
+    now: i64,
+) -> Result<()> {
+    if authority == owner {
+        return Ok(());
+    }
+    let session = session.ok_or(ErrorCode::InvalidSession)?;
+    require_keys_eq!(session.owner, *owner, ErrorCode::InvalidSession);
+    require_keys_eq!(session.session_key, *authority, ErrorCode::InvalidSession);
+    require!(now < session.expires_at, ErrorCode::SessionExpired);
     require!(
-        betting_deadline > Clock::get()?.unix_timestamp,
-        ErrorCode::DeadlinePassed
+        session.allowed_instructions & instruction != 0,
+        ErrorCode::SessionInstructionNotAllowed
     );
+    require!(
+        session.spent + spend <= session.spend_cap,
+        ErrorCode::SessionSpendCapExceeded
+    );
+    session.spent += spend;
+    Ok(())
+}
 
-    let bet_pool = &mut ctx.accounts.bet_pool;
-    bet_pool.fixture_id = fixture_id;
-    bet_pool.player_id = player_id;
-    bet_pool.stat_name = stat_name;
-    bet_pool.stat_line = stat_line;
-    bet_pool.sport_name = sport_name;
-    bet_pool.deadline = betting_deadline;
-    bet_pool.total_over_amount = 0;
-    bet_pool.total_under_amount = 0;
-    bet_pool.fee_vault = ctx.accounts.fee_vault.key();
-    bet_pool.result_published = false;
-    bet_pool.final_stat = 0;
-    bet_pool.bump = ctx.bumps.bet_pool;
+// Instructions take the `[b"blocked", wallet]` PDA for every wallet they touch. It is only
+// initialized while the wallet is blocked, so an empty account means the wallet is clear.
+fn require_not_blocked(block: &AccountInfo) -> Result<()> {
+    require!(block.data_is_empty(), ErrorCode::WalletBlocked);
+    Ok(())
+}
 
-    msg!("🔒 fixture_id: {:?}", fixture_id.to_le_bytes());
-    msg!("🔒 sport_name: {:?}", sport_name);
-    msg!("🔒 player_id: {:?}", player_id.to_bytes());
-    msg!("🔒 stat_name: {:?}", stat_name);
-    msg!("🔒 stat_line: {:?}", stat_line.to_le_bytes());
+// Pays the recipient, or parks the funds in their block PDA if they are blocked.
+// Returns true when the payout was held.
+fn pay_or_hold<'info>(
+    bet_vault: &mut Account<'info, BetVault>,
+    pool: Pubkey,
+    recipient: &AccountInfo<'info>,
+    block: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<bool> {
+    if block.data_is_empty() {
+        withdraw_from_vault(bet_vault, &[pool], recipient, amount)?;
+        return Ok(false);
+    }
 
-    // Optional: Debug PDA derivation (useful for verifying correct seeds)
-    let seeds = &[
-        b"bet_pool",
-        &fixture_id.to_le_bytes(),
-        &sport_name[..],
-        player_id.as_ref(),
-        &stat_name,
-        &stat_line.to_le_bytes(),
-    ];
-    let (expected_pda, bump) = Pubkey::find_program_address(seeds, ctx.program_id);
-    msg!("🧠 On-chain PDA: {}", expected_pda);
-    msg!("🧠 Bump: {}", bump);
-    msg!("🧠 Anchor bump: {}", ctx.bumps.bet_pool);
+    let mut blocked = BlockedWallet::try_deserialize(&mut &block.try_borrow_data()?[..])?;
+    require_keys_eq!(blocked.wallet, recipient.key(), ErrorCode::InvalidBlockAccount);
+    withdraw_from_vault(bet_vault, &[pool], block, amount)?;
+    blocked.held_amount += amount;
+    blocked.try_serialize(&mut &mut block.try_borrow_mut_data()?[..])?;
+    msg!("🧊 Held {} lamports for blocked wallet {}", amount, recipient.key());
+    Ok(true)
+}
 
+fn require_attested(
+    bet_pool: &BetPool,
+    attestation: Option<&Account<Attestation>>,
+    wallet: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    if !bet_pool.requires_attestation {
+        return Ok(());
+    }
+    let attestation = attestation.ok_or(ErrorCode::AttestationRequired)?;
+    require_keys_eq!(attestation.wallet, *wallet, ErrorCode::AttestationRequired);
+    require!(now < attestation.expires_at, ErrorCode::AttestationExpired);
+    require!(
+        bet_pool.allows_jurisdiction(&attestation.jurisdiction),
+        ErrorCode::JurisdictionNotAllowed
+    );
     Ok(())
 }
 
+fn validate_config_params(params: &ConfigParams) -> Result<()> {
+    require!(params.claim_period > 0, ErrorCode::InvalidClaimPeriod);
+    require!(params.resolution_timeout > 0, ErrorCode::InvalidResolutionTimeout);
+    require!(params.dispute_window >= 0, ErrorCode::InvalidDisputeWindow);
+    require!(params.dispute_timeout > 0, ErrorCode::InvalidDisputeTimeout);
+    require!(params.dispute_bond > 0, ErrorCode::InvalidDisputeBond);
+    require!(params.correction_window >= 0, ErrorCode::InvalidCorrectionWindow);
+    Ok(())
+}
 
-pub fn place_bet(
-    ctx: Context<PlaceBet>,
-    _fixture_id: u64,
-    _player_id: Pubkey,
-    _stat_name: [u8; 32],
-    _stat_line: u32,
-    bet_amount: u64,
-    pick_side: bool,
-    _sport_name: [u8; 32],
+fn publish_stat(
+    bet_pool: &mut BetPool,
+    fixture: &Fixture,
+    sport: &Sport,
+    final_stat: u32,
+    authority: Pubkey,
+    dispute_window: i64,
 ) -> Result<()> {
-    // 1. Save current nonce and increment it
-    let nonce = ctx.accounts.user_nonce.count;
-    ctx.accounts.user_nonce.count += 1;
+    require!(fixture.status != FixtureStatus::Postponed, ErrorCode::FixturePostponed);
+    require!(bet_pool.stat_component_count == 0, ErrorCode::CompositeResultRequired);
+    let stat_type = sport
+        .stat_type(&bet_pool.stat_name)
+        .ok_or(ErrorCode::InvalidStatType)?;
+    require!(stat_type.is_plausible(final_stat), ErrorCode::ImplausibleStatValue);
+
+    bet_pool.propose_result(final_stat, authority, Clock::get()?.unix_timestamp, dispute_window)
+}
 
-    let user_pick = &mut ctx.accounts.user_pick;
-    let sport_name = ctx.accounts.bet_pool.sport_name;
-    let bet_pool = &mut ctx.accounts.bet_pool;
+fn publish_composite_stat(
+    bet_pool: &mut BetPool,
+    fixture: &Fixture,
+    sport: &Sport,
+    component_values: &[u32],
+    authority: Pubkey,
+    dispute_window: i64,
+) -> Result<u32> {
+    require!(fixture.status != FixtureStatus::Postponed, ErrorCode::FixturePostponed);
+    let count = bet_pool.stat_component_count as usize;
+    require!(count > 0, ErrorCode::NotCompositePool);
+    require!(component_values.len() == count, ErrorCode::ComponentCountMismatch);
+
+    for (component, value) in bet_pool.stat_components.iter().zip(component_values) {
+        let stat_type = sport
+            .stat_type(&component.stat_name)
+            .ok_or(ErrorCode::InvalidStatType)?;
+        require!(stat_type.is_plausible(*value), ErrorCode::ImplausibleStatValue);
+    }
 
-    let fee = (bet_amount * 5) / 100;
-    let net_amount = bet_amount - fee;
+    let final_stat = bet_pool.composite_stat(component_values)?;
+    let stat_type = sport
+        .stat_type(&bet_pool.stat_name)
+        .ok_or(ErrorCode::InvalidStatType)?;
+    require!(stat_type.is_plausible(final_stat), ErrorCode::ImplausibleStatValue);
 
-    user_pick.owner = ctx.accounts.bettor.key();
-    user_pick.bet_amount = net_amount;
-    user_pick.pick_side = pick_side;
-    user_pick.pool = bet_pool.key();
-    user_pick.claimed = false;
-    user_pick.mint = ctx.accounts.mint.key();
-    user_pick.bump = ctx.bumps.user_pick;
-    user_pick.sport_name = sport_name;
+    bet_pool.component_values = [0; MAX_STAT_COMPONENTS];
+    bet_pool.component_values[..count].copy_from_slice(component_values);
+    bet_pool.propose_result(final_stat, authority, Clock::get()?.unix_timestamp, dispute_window)?;
+    Ok(final_stat)
+}
 
-    if pick_side {
-        bet_pool.total_over_amount += net_amount;
-    } else {
-        bet_pool.total_under_amount += net_amount;
+fn validate_council(members: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !members.is_empty() && members.len() <= MAX_COUNCIL_MEMBERS,
+        ErrorCode::InvalidCouncil
+    );
+    require!(
+        threshold > 0 && threshold as usize <= members.len(),
+        ErrorCode::InvalidCouncil
+    );
+    require!(
+        members.iter().enumerate().all(|(i, m)| !members[..i].contains(m)),
+        ErrorCode::InvalidCouncil
+    );
+    Ok(())
+}
+
+fn validate_pool_spec(
+    fixture: &Fixture,
+    sport: &Sport,
+    player: &Player,
+    spec: &PoolSpec,
+) -> Result<()> {
+    require!(spec.stat_line > 0, ErrorCode::InvalidStatLine);
+    let stat_type = sport
+        .stat_type(&spec.stat_name)
+        .ok_or(ErrorCode::InvalidStatType)?;
+    require!(stat_type.is_plausible(spec.stat_line), ErrorCode::InvalidStatLine);
+    require!(player.sport_name == fixture.sport_name, ErrorCode::PlayerMismatch);
+    require!(player.active, ErrorCode::PlayerInactive);
+    require!(
+        fixture.status == FixtureStatus::Scheduled,
+        ErrorCode::FixtureNotScheduled
+    );
+    require!(
+        fixture.scheduled_start > Clock::get()?.unix_timestamp,
+        ErrorCode::DeadlinePassed
+    );
+    Ok(())
+}
+
+// Creates a program-owned PDA, topping up instead of failing if someone pre-funded it
+fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    target: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let required = Rent::get()?.minimum_balance(space);
+    let current = target.lamports();
+    let accounts = [payer.clone(), target.clone(), system_program.clone()];
+
+    if current == 0 {
+        invoke_signed(
+            &system_instruction::create_account(payer.key, target.key, required, space as u64, &crate::ID),
+            &accounts,
+            &[signer_seeds],
+        )?;
+        return Ok(());
     }
 
-    invoke(
-        &system_instruction::transfer(
-            &ctx.accounts.bettor.key(),
-            &ctx.accounts.fee_vault.key(),
-            fee,
-        ),
-        &[
-            ctx.accounts.bettor.to_account_info(),
-            ctx.accounts.fee_vault.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
+    if required > current {
+        invoke(
+            &system_instruction::transfer(payer.key, target.key, required - current),
+            &accounts,
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(target.key, space as u64),
+        &accounts,
+        &[signer_seeds],
     )?;
-
-    invoke(
-        &system_instruction::transfer(
-            &ctx.accounts.bettor.key(),
-            &ctx.accounts.bet_vault.key(),
-            net_amount,
-        ),
-        &[
-            ctx.accounts.bettor.to_account_info(),
-            ctx.accounts.bet_vault.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
+    invoke_signed(
+        &system_instruction::assign(target.key, &crate::ID),
+        &accounts,
+        &[signer_seeds],
     )?;
+    Ok(())
+}
 
-    let mint_authority_bump = ctx.bumps.mint_authority;
-    let mint_key = user_pick.key();
-    let mint_seeds: &[&[u8]] = &[b"mint", mint_key.as_ref(), &[mint_authority_bump]];
-    let signer_seeds: &[&[&[u8]]] = &[mint_seeds];
-
+// Shared by settle_claim and crank_settle: validates the pick and marks it settled
+fn settle_pick(
+    bet_pool: &mut Account<BetPool>,
+    user_pick: &mut UserPick,
+    voided: bool,
+    claim_period: i64,
+) -> Result<Settlement> {
+    require!(user_pick.pool == bet_pool.key(), ErrorCode::Unauthorized);
+    // A postponed fixture voids the pool, so every pick is refunded
+    require!(
+        bet_pool.status == PoolStatus::Finalized || voided,
+        ErrorCode::PoolNotSettled
+    );
+    require!(!user_pick.claimed, ErrorCode::AlreadyClaimed);
+    let claim_deadline = bet_pool.effective_claim_deadline(claim_period);
+    require!(
+        Clock::get()?.unix_timestamp < claim_deadline,
+        ErrorCode::ClaimDeadlinePassed
+    );
 
-        let cpi_accounts = token::InitializeMint {
-            mint: ctx.accounts.mint.to_account_info(),
-            rent: ctx.accounts.rent.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
-        );
+    // One mode per pool: a finalized result stands even if the fixture is postponed
+    // afterwards, and a postponed pool can no longer be finalized
+    let voided = voided && bet_pool.status != PoolStatus::Finalized;
+    let mut settlement = bet_pool.settlement_for(user_pick, voided);
+    if let Settlement::Won(_) = settlement {
+        bet_pool.winning_stake_claimed += user_pick.bet_amount;
+        // The last winner takes the exact remainder so no rounding dust is stranded
+        if bet_pool.winning_stake_claimed == bet_pool.winning_pool() {
+            settlement = Settlement::Won(bet_pool.total_pool() - bet_pool.total_paid_out);
+        }
+    }
+    bet_pool.record_payout(settlement.payout())?;
 
-        if ctx.accounts.mint.to_account_info().try_borrow_data()?[0] == 0 {
-    let cpi_accounts = token::InitializeMint {
-        mint: ctx.accounts.mint.to_account_info(),
-        rent: ctx.accounts.rent.to_account_info(),
-    };
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts,
-        signer_seeds,
-    );
-    token::initialize_mint(
-        cpi_ctx,
-        0,
-        &ctx.accounts.mint_authority.key(),
-        Some(&ctx.accounts.mint_authority.key()),
-    )?;
+    user_pick.claimed = true;
+    bet_pool.settled_count += 1;
+    Ok(settlement)
 }
 
-    if ctx.accounts.user_token_account.to_account_info().try_borrow_data()?.len() == 0 {
-        let ata_ctx = CpiContext::new(
-            ctx.accounts.associated_token_program.to_account_info(),
-            associated_token::Create {
-                payer: ctx.accounts.bettor.to_account_info(),
-                associated_token: ctx.accounts.user_token_account.to_account_info(),
-                authority: ctx.accounts.bettor.to_account_info(),
-                mint: ctx.accounts.mint.to_account_info(),
-                system_program: ctx.accounts.system_program.to_account_info(),
-                token_program: ctx.accounts.token_program.to_account_info(),
-            },
-        );
-        associated_token::create(ata_ctx)?;
+// All vault withdrawals go through here: checks the vault belongs to the caller's pool
+// and never lets it dip below its rent-exempt minimum
+fn withdraw_from_vault<'info, T>(
+    vault: &mut Account<'info, T>,
+    owners: &[Pubkey],
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()>
+where
+    T: LamportVault + AccountSerialize + AccountDeserialize + Owner + Clone,
+{
+    require!(owners.contains(&vault.owner_key()), ErrorCode::InvalidVaultOwner);
+    require!(vault_excess(vault)? >= amount, ErrorCode::VaultOverdrawn);
+    if amount == 0 {
+        return Ok(());
     }
 
-    let cpi_accounts = MintTo {
-        mint: ctx.accounts.mint.to_account_info(),
-        to: ctx.accounts.user_token_account.to_account_info(),
-        authority: ctx.accounts.mint_authority.to_account_info(),
-    };
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts,
-        signer_seeds,
-    );
-    token::mint_to(cpi_ctx, 1)?;
-
+    let info = vault.to_account_info();
+    **info.try_borrow_mut_lamports()? -= amount;
+    **to.try_borrow_mut_lamports()? += amount;
+    vault.record_withdrawal(amount);
     Ok(())
 }
 
-pub fn admin_update_result(
-    ctx: Context<AdminUpdateResult>,
-    new_final_stat: u32,
-) -> Result<()> {
+// Lamports held above the rent-exempt minimum
+fn vault_excess<T>(vault: &Account<T>) -> Result<u64>
+where
+    T: AccountSerialize + AccountDeserialize + Owner + Clone,
+{
+    let info = vault.to_account_info();
+    let rent_floor = Rent::get()?.minimum_balance(info.data_len());
+    Ok(info.lamports().saturating_sub(rent_floor))
+}
 
-    // Require only the admin can do this!
-    let admin_pk = Pubkey::from_str(ADMIN_PUBKEY).unwrap();
-require!(ctx.accounts.authority.key() == admin_pk, ErrorCode::Unauthorized);
+fn signed_difference(actual: u64, expected: u64) -> i64 {
+    (actual as i128 - expected as i128).clamp(i64::MIN as i128, i64::MAX as i128) as i64
+}
 
-    let bet_pool = &mut ctx.accounts.bet_pool;
-    bet_pool.result_published = true;
-    bet_pool.final_stat = new_final_stat;
+// Sport and stat names feed PDA seeds, so "NFL"/"nfl" must not both be valid
+pub fn is_canonical_name(name: &[u8; 32]) -> bool {
+    let len = name.iter().position(|b| *b == 0).unwrap_or(name.len());
+    len > 0
+        && name[..len]
+            .iter()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || *b == b'_')
+        && name[len..].iter().all(|b| *b == 0)
+}
 
+
+
+
+#[program]
+pub mod fantasy_sports {
+    use super::*;
+
+pub fn initialize_config(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+    require_admin(&ctx.accounts.admin.key())?;
+
+    validate_config_params(&params)?;
+
+    let config = &mut ctx.accounts.config;
+    config.apply(&params);
+    config.royalty_bps = DEFAULT_ROYALTY_BPS;
+    config.bump = ctx.bumps.config;
+
+    msg!("⚙️ Treasury set to {}", config.treasury);
     Ok(())
 }
 
-pub fn settle_claim(ctx: Context<SettleClaim>) -> Result<()> {
-    let bet_pool = &ctx.accounts.bet_pool;
-    let user_pick = &mut ctx.accounts.user_pick;
-
-    require!(user_pick.pool == bet_pool.key(), ErrorCode::Unauthorized);
-    require!(bet_pool.result_published, ErrorCode::PoolNotSettled);
-    require!(!user_pick.claimed, ErrorCode::AlreadyClaimed);
+pub fn initialize_jackpot_vault(ctx: Context<InitializeJackpotVault>) -> Result<()> {
+    require_admin(&ctx.accounts.admin.key())?;
+    msg!("🎰 Jackpot vault: {}", ctx.accounts.jackpot_vault.key());
+    Ok(())
+}
 
+pub fn initialize_insurance_fund(ctx: Context<InitializeInsuranceFund>) -> Result<()> {
+    require_admin(&ctx.accounts.admin.key())?;
+    msg!("🛡️ Insurance fund: {}", ctx.accounts.insurance_fund.key());
+    Ok(())
+}
 
-    // Handle one-sided refund (no opposite picks)
-    let has_only_over = bet_pool.total_under_amount == 0;
-    let has_only_under = bet_pool.total_over_amount == 0;
+pub fn grant_role(ctx: Context<GrantRole>, role: Role, grantee: Pubkey) -> Result<()> {
+    require_admin(&ctx.accounts.admin.key())?;
 
-    if has_only_over || has_only_under {
-        let refund = user_pick.bet_amount;
-        msg!("💸 Refunding {} lamports to {}", refund, ctx.accounts.recipient.key());
+    let role_grant = &mut ctx.accounts.role_grant;
+    role_grant.grantee = grantee;
+    role_grant.role = role;
+    role_grant.granted_at = Clock::get()?.unix_timestamp;
+    role_grant.bump = ctx.bumps.role_grant;
 
-        **ctx.accounts.bet_vault.to_account_info().try_borrow_mut_lamports()? -= refund;
-        **ctx.accounts.recipient.to_account_info().try_borrow_mut_lamports()? += refund;
+    msg!("🔑 Granted {:?} to {}", role, grantee);
+    Ok(())
+}
 
-        user_pick.claimed = true;
-        return Ok(());
-    }
+pub fn revoke_role(ctx: Context<RevokeRole>) -> Result<()> {
+    require_admin(&ctx.accounts.admin.key())?;
 
-    // Regular outcome logic
-    let over_wins = bet_pool.final_stat > bet_pool.stat_line;
-    let under_wins = bet_pool.final_stat < bet_pool.stat_line;
+    let role_grant = &ctx.accounts.role_grant;
+    msg!("🔑 Revoked {:?} from {}", role_grant.role, role_grant.grantee);
+    Ok(())
+}
 
-    let winner_is_over = match (over_wins, under_wins) {
-        (true, false) => true,
-        (false, true) => false,
-        _ => {
-            user_pick.claimed = true;
-            return Ok(()); // Push/tie, no payout
+// The guardian may only raise flags; clearing any flag needs the Pauser role
+pub fn set_pause(
+    ctx: Context<SetPause>,
+    scope: PauseScope,
+    flags: u8,
+    reason_code: u16,
+) -> Result<()> {
+    require!(flags & !PAUSE_ALL == 0, ErrorCode::InvalidPauseFlags);
+
+    let authority = ctx.accounts.authority.key();
+    let guardian = ctx.accounts.config.guardian;
+    let accounts = ctx.accounts;
+    let (target, paused_flags) = match scope {
+        PauseScope::Global => (accounts.config.key(), &mut accounts.config.paused_flags),
+        PauseScope::Sport => {
+            let sport = accounts.sport.as_mut().ok_or(ErrorCode::PauseTargetMissing)?;
+            (sport.key(), &mut sport.paused_flags)
+        }
+        PauseScope::Pool => {
+            let bet_pool = accounts.bet_pool.as_mut().ok_or(ErrorCode::PauseTargetMissing)?;
+            (bet_pool.key(), &mut bet_pool.paused_flags)
         }
     };
+    let previous_flags = *paused_flags;
 
-    if user_pick.pick_side != winner_is_over {
-        user_pick.claimed = true;
-        return Ok(()); // Lost
+    let unpausing = previous_flags & !flags != 0;
+    if unpausing || authority != guardian {
+        require_role(&authority, accounts.role_grant.as_ref(), Role::Pauser)?;
     }
+    *paused_flags = flags;
+
+    emit!(PauseChanged {
+        scope,
+        target,
+        previous_flags,
+        flags,
+        authority,
+        reason_code,
+    });
+    msg!("⏸️ {:?} {} pause flags {:#05b} -> {:#05b}", scope, target, previous_flags, flags);
+    Ok(())
+}
 
-    // Payout calculation
-    let total_pool = bet_pool.total_over_amount + bet_pool.total_under_amount;
-    let winner_pool = if winner_is_over {
-        bet_pool.total_over_amount
-    } else {
-        bet_pool.total_under_amount
-    };
+// Re-issuing overwrites the previous attestation for the wallet
+pub fn issue_attestation(
+    ctx: Context<IssueAttestation>,
+    wallet: Pubkey,
+    jurisdiction: [u8; 2],
+    expires_at: i64,
+) -> Result<()> {
+    require_role(&ctx.accounts.authority.key(), ctx.accounts.role_grant.as_ref(), Role::Compliance)?;
+    let now = Clock::get()?.unix_timestamp;
+    require!(expires_at > now, ErrorCode::AttestationExpired);
+
+    let attestation = &mut ctx.accounts.attestation;
+    attestation.wallet = wallet;
+    attestation.issuer = ctx.accounts.authority.key();
+    attestation.jurisdiction = jurisdiction;
+    attestation.issued_at = now;
+    attestation.expires_at = expires_at;
+    attestation.bump = ctx.bumps.attestation;
+
+    msg!("🪪 Attested {} until {}", wallet, expires_at);
+    Ok(())
+}
 
-    let user_share = (user_pick.bet_amount as u128)
-        .checked_mul(total_pool as u128)
-        .unwrap()
-        / (winner_pool as u128);
-    let payout = user_share as u64;
+// Closing the PDA revokes immediately
+pub fn revoke_attestation(ctx: Context<RevokeAttestation>) -> Result<()> {
+    require_role(&ctx.accounts.authority.key(), ctx.accounts.role_grant.as_ref(), Role::Compliance)?;
+    msg!("🪪 Revoked attestation for {}", ctx.accounts.attestation.wallet);
+    Ok(())
+}
 
-    msg!("🏆 Paying {} lamports to {}", payout, ctx.accounts.recipient.key());
+pub fn block_wallet(ctx: Context<BlockWallet>, wallet: Pubkey, reason_code: u16) -> Result<()> {
+    require_role(&ctx.accounts.authority.key(), ctx.accounts.role_grant.as_ref(), Role::Compliance)?;
 
-    **ctx.accounts.bet_vault.to_account_info().try_borrow_mut_lamports()? -= payout;
-    **ctx.accounts.recipient.to_account_info().try_borrow_mut_lamports()? += payout;
+    let blocked = &mut ctx.accounts.blocked_wallet;
+    blocked.wallet = wallet;
+    blocked.blocked_by = ctx.accounts.authority.key();
+    blocked.reason_code = reason_code;
+    blocked.blocked_at = Clock::get()?.unix_timestamp;
+    blocked.held_amount = 0;
+    blocked.bump = ctx.bumps.blocked_wallet;
 
-    user_pick.claimed = true;
+    msg!("⛔ Blocked {} (reason {})", wallet, reason_code);
     Ok(())
 }
 
+// Releases any held winnings to the wallet and closes the block PDA
+pub fn unblock_wallet(ctx: Context<UnblockWallet>) -> Result<()> {
+    require_role(&ctx.accounts.authority.key(), ctx.accounts.role_grant.as_ref(), Role::Compliance)?;
 
-pub fn delist_pick(ctx: Context<DelistPick>) -> Result<()> {
-let user_pick_key = ctx.accounts.user_pick.key(); // <-- Immutable borrow first
+    let held = ctx.accounts.blocked_wallet.held_amount;
+    let blocked_info = ctx.accounts.blocked_wallet.to_account_info();
+    **blocked_info.try_borrow_mut_lamports()? -= held;
+    **ctx.accounts.wallet.to_account_info().try_borrow_mut_lamports()? += held;
+    ctx.accounts.blocked_wallet.held_amount = 0;
 
-let bump = ctx.bumps.escrow_pda;
+    msg!("✅ Unblocked {}, released {} lamports", ctx.accounts.wallet.key(), held);
+    Ok(())
+}
 
-let seeds: &[&[u8]] = &[
-    b"escrow",
-    user_pick_key.as_ref(), // use the key from above
-    &[bump],
-];
-let signer = &[seeds];
+pub fn initialize_sport(ctx: Context<InitializeSport>, sport_name: [u8; 32]) -> Result<()> {
+    require_admin(&ctx.accounts.admin.key())?;
+    require!(is_canonical_name(&sport_name), ErrorCode::NonCanonicalName);
 
-let cpi_accounts = Transfer {
-    from: ctx.accounts.escrow_token_account.to_account_info(),
-    to: ctx.accounts.seller_token_account.to_account_info(),
-    authority: ctx.accounts.escrow_pda.to_account_info(),
-};
+    let sport = &mut ctx.accounts.sport;
+    sport.sport_name = sport_name;
+    sport.stat_types = [StatType::default(); MAX_STAT_TYPES];
+    sport.stat_type_count = 0;
+    sport.bump = ctx.bumps.sport;
 
-let cpi_ctx = CpiContext::new_with_signer(
-    ctx.accounts.token_program.to_account_info(),
-    cpi_accounts,
-    signer,
-);
+    msg!("🏆 Registered sport {}", sport.key());
+    Ok(())
+}
 
-token::transfer(cpi_ctx, 1)?;
+pub fn upsert_stat_type(ctx: Context<UpdateSport>, stat_type: StatType) -> Result<()> {
+    require_admin(&ctx.accounts.authority.key())?;
+    require!(is_canonical_name(&stat_type.stat_name), ErrorCode::NonCanonicalName);
+    require!(stat_type.min_value <= stat_type.max_value, ErrorCode::InvalidStatRange);
+
+    let sport = &mut ctx.accounts.sport;
+    let count = sport.stat_type_count as usize;
+    match sport.stat_types[..count]
+        .iter()
+        .position(|t| t.stat_name == stat_type.stat_name)
+    {
+        Some(index) => sport.stat_types[index] = stat_type,
+        None => {
+            require!(count < MAX_STAT_TYPES, ErrorCode::SportStatTypesFull);
+            sport.stat_types[count] = stat_type;
+            sport.stat_type_count += 1;
+        }
+    }
 
-// Now can mutably borrow `user_pick`
-let user_pick = &mut ctx.accounts.user_pick;
-user_pick.for_sale = false;
+    msg!("📏 Stat type range {}..={}", stat_type.min_value, stat_type.max_value);
+    Ok(())
+}
 
-Ok(())
+pub fn initialize_fixture(
+    ctx: Context<InitializeFixture>,
+    fixture_id: u64,
+    sport_name: [u8; 32],
+    home_team: [u8; 32],
+    away_team: [u8; 32],
+    scheduled_start: i64,
+) -> Result<()> {
+    require_role(&ctx.accounts.admin.key(), ctx.accounts.role_grant.as_ref(), Role::PoolCreator)?;
+    require!(fixture_id > 0, ErrorCode::InvalidFixture);
+    require!(
+        scheduled_start > Clock::get()?.unix_timestamp,
+        ErrorCode::DeadlinePassed
+    );
+
+    let fixture = &mut ctx.accounts.fixture;
+    fixture.fixture_id = fixture_id;
+    fixture.sport_name = sport_name;
+    fixture.home_team = home_team;
+    fixture.away_team = away_team;
+    fixture.scheduled_start = scheduled_start;
+    fixture.status = FixtureStatus::Scheduled;
+    fixture.bump = ctx.bumps.fixture;
+
+    msg!("🏟️ Fixture {} scheduled for {}", fixture_id, scheduled_start);
+    Ok(())
 }
 
+pub fn update_fixture_status(
+    ctx: Context<UpdateFixture>,
+    status: FixtureStatus,
+) -> Result<()> {
+    require_role(&ctx.accounts.authority.key(), ctx.accounts.role_grant.as_ref(), Role::Resolver)?;
 
+    let fixture = &mut ctx.accounts.fixture;
+    require!(
+        fixture.status.can_transition_to(status),
+        ErrorCode::InvalidFixtureStatus
+    );
+    fixture.status = status;
 
-pub fn buy_pick_nft(ctx: Context<BuyPickNFT>, sale_price: u64) -> Result<()> {
-    let user_pick = &mut ctx.accounts.user_pick;
+    msg!("🏟️ Fixture {} is now {:?}", fixture.fixture_id, status);
+    Ok(())
+}
 
-    require!(!user_pick.claimed, ErrorCode::AlreadyClaimed);
-    require!(!ctx.accounts.pool.result_published, ErrorCode::PoolNotSettled);
+pub fn register_player(
+    ctx: Context<RegisterPlayer>,
+    sport_name: [u8; 32],
+    external_id: u64,
+    display_name: [u8; 32],
+    team: [u8; 32],
+) -> Result<()> {
+    require_role(&ctx.accounts.admin.key(), ctx.accounts.role_grant.as_ref(), Role::PoolCreator)?;
 
-    let royalty_fee = (sale_price * 25) / 1000;
-    let seller_amount = sale_price - royalty_fee;
+    let player = &mut ctx.accounts.player;
+    player.sport_name = sport_name;
+    player.external_id = external_id;
+    player.display_name = display_name;
+    player.team = team;
+    player.active = true;
+    player.bump = ctx.bumps.player;
 
-    // Pay seller
-    invoke(
-        &system_instruction::transfer(
-            &ctx.accounts.buyer.key(),
-            &ctx.accounts.seller.key(),
-            seller_amount,
-        ),
-        &[
-            ctx.accounts.buyer.to_account_info(),
-            ctx.accounts.seller.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-    )?;
+    msg!("🏈 Registered player {}", player.key());
+    Ok(())
+}
 
-    // Pay royalty
-    invoke(
-        &system_instruction::transfer(
-            &ctx.accounts.buyer.key(),
-            &ctx.accounts.royalty_vault.key(),
-            royalty_fee,
-        ),
-        &[
-            ctx.accounts.buyer.to_account_info(),
-            ctx.accounts.royalty_vault.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-    )?;
+pub fn update_player(
+    ctx: Context<UpdatePlayer>,
+    display_name: [u8; 32],
+    team: [u8; 32],
+    active: bool,
+) -> Result<()> {
+    require_role(&ctx.accounts.authority.key(), ctx.accounts.role_grant.as_ref(), Role::PoolCreator)?;
 
-    // Derive signer seeds
-    let user_pick_key = user_pick.key();
-    let seeds: &[&[u8]] = &[
-        b"escrow",
-        user_pick_key.as_ref(),
-        // Note: you must use the actual bump here if you don't use #[account(..., bump)]
-        &[Pubkey::find_program_address(&[b"escrow", user_pick_key.as_ref()], ctx.program_id).1],
-    ];
-    let signer = &[seeds]; // type: &[&[&[u8]]]
+    let player = &mut ctx.accounts.player;
+    player.display_name = display_name;
+    player.team = team;
+    player.active = active;
 
-    // Define CPI accounts
-    let cpi_accounts = anchor_spl::token::Transfer {
-        from: ctx.accounts.escrow_token_account.to_account_info(),
-        to: ctx.accounts.buyer_token_account.to_account_info(),
-        authority: ctx.accounts.escrow_pda.to_account_info(),
-    };
+    msg!("🏈 Player {} active: {}", player.key(), active);
+    Ok(())
+}
 
-    // Create CPI context with signer
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts,
-        signer,
+pub fn initialize_bet_pool(
+    ctx: Context<InitializeBetPool>,
+    fixture_id: u64,
+    player_id: Pubkey,
+    stat_name: [u8; 32],
+    stat_line: u32,
+    sport_name: [u8; 32],
+) -> Result<()> {
+    require_role(&ctx.accounts.admin.key(), ctx.accounts.role_grant.as_ref(), Role::PoolCreator)?;
+
+    msg!("🧪 fixture_id: {}", fixture_id);
+    msg!("🧪 stat_line: {}", stat_line);
+    msg!("🧪 player_id: {}", player_id);
+
+    require!(fixture_id > 0, ErrorCode::InvalidFixture);
+    require!(
+        ctx.accounts.fixture.sport_name == sport_name,
+        ErrorCode::FixtureMismatch
     );
 
-    // Perform NFT transfer from escrow to buyer
-    token::transfer(cpi_ctx, 1)?;
+    let spec = PoolSpec { player_id, stat_name, stat_line };
+    validate_pool_spec(
+        &ctx.accounts.fixture,
+        &ctx.accounts.sport,
+        &ctx.accounts.player,
+        &spec,
+    )?;
 
-    // Update on-chain metadata
-    user_pick.owner = ctx.accounts.buyer.key();
-    user_pick.for_sale = false;
+    // Pools inherit the fixture's kickoff as their betting deadline
+    let bet_pool = BetPool::new(
+        &ctx.accounts.fixture,
+        &spec,
+        ctx.accounts.fee_vault.key(),
+        &ctx.accounts.config,
+        ctx.bumps.bet_pool,
+    );
+    ctx.accounts.bet_pool.set_inner(bet_pool);
+
+    let pool_key = ctx.accounts.bet_pool.key();
+    ctx.accounts.fee_vault.set_inner(FeeVault {
+        owner: pool_key,
+        bump: ctx.bumps.fee_vault,
+        ..Default::default()
+    });
+    ctx.accounts.bet_vault.set_inner(BetVault {
+        owner: pool_key,
+        bump: ctx.bumps.bet_vault,
+        ..Default::default()
+    });
+
+    msg!("🔒 fixture_id: {:?}", fixture_id.to_le_bytes());
+    msg!("🔒 sport_name: {:?}", sport_name);
+    msg!("🔒 player_id: {:?}", player_id.to_bytes());
+    msg!("🔒 stat_name: {:?}", stat_name);
+    msg!("🔒 stat_line: {:?}", stat_line.to_le_bytes());
+
+    // Optional: Debug PDA derivation (useful for verifying correct seeds)
+    let seeds = &[
+        b"bet_pool",
+        &fixture_id.to_le_bytes(),
+        &sport_name[..],
+        player_id.as_ref(),
+        &stat_name,
+        &stat_line.to_le_bytes(),
+    ];
+    let (expected_pda, bump) = Pubkey::find_program_address(seeds, ctx.program_id);
+    msg!("🧠 On-chain PDA: {}", expected_pda);
+    msg!("🧠 Bump: {}", bump);
+    msg!("🧠 Anchor bump: {}", ctx.bumps.bet_pool);
 
     Ok(())
 }
 
 
-        pub fn list_pick_nft(ctx: Context<ListPickNFT>) -> Result<()> {
-        // Transfer NFT from seller to escrow
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.seller_token_account.to_account_info(),
-            to: ctx.accounts.escrow_token_account.to_account_info(),
-            authority: ctx.accounts.seller.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
-        token::transfer(cpi_ctx, 1)?;
-        ctx.accounts.user_pick.for_sale = true;
-        Ok(())
+pub fn initialize_bet_pools_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, InitializeBetPoolsBatch<'info>>,
+    pools: Vec<PoolSpec>,
+) -> Result<()> {
+    require_role(&ctx.accounts.admin.key(), ctx.accounts.role_grant.as_ref(), Role::PoolCreator)?;
+    require!(!pools.is_empty(), ErrorCode::EmptyBatch);
+    // Each entry supplies [bet_pool, bet_vault, player]
+    require!(
+        ctx.remaining_accounts.len() == pools.len() * 3,
+        ErrorCode::BatchAccountsMismatch
+    );
+
+    let fixture = &ctx.accounts.fixture;
+    if ctx.accounts.fee_vault.owner == Pubkey::default() {
+        ctx.accounts.fee_vault.owner = fixture.key();
+        ctx.accounts.fee_vault.bump = ctx.bumps.fee_vault;
     }
+    let fixture_id_bytes = fixture.fixture_id.to_le_bytes();
+    let admin = ctx.accounts.admin.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
 
-pub fn reclaim_unsold_pick(ctx: Context<ReclaimUnsoldPick>) -> Result<()> {
-    let user_pick = &mut ctx.accounts.user_pick;
-    user_pick.for_sale = false;
+    for (spec, accounts) in pools.iter().zip(ctx.remaining_accounts.chunks(3)) {
+        let (pool_info, vault_info, player_info) = (&accounts[0], &accounts[1], &accounts[2]);
 
-    let user_pick_key = user_pick.key();
-    let bump = ctx.bumps.escrow_pda;
-    let seeds: [&[u8]; 3] = [b"escrow", user_pick_key.as_ref(), &[bump]];
-    let signer: &[&[&[u8]]] = &[&seeds];
+        require_keys_eq!(player_info.key(), spec.player_id, ErrorCode::PlayerMismatch);
+        let player: Account<Player> = Account::try_from(player_info)?;
+        validate_pool_spec(fixture, &ctx.accounts.sport, &player, spec)?;
 
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.escrow_token_account.to_account_info(),
-        to: ctx.accounts.seller_token_account.to_account_info(),
-        authority: ctx.accounts.escrow_pda.to_account_info(),
-    };
+        let stat_line_bytes = spec.stat_line.to_le_bytes();
+        let pool_seeds: &[&[u8]] = &[
+            b"bet_pool",
+            &fixture_id_bytes,
+            &fixture.sport_name[..],
+            spec.player_id.as_ref(),
+            &spec.stat_name,
+            &stat_line_bytes,
+        ];
+        let (pool_key, pool_bump) = Pubkey::find_program_address(pool_seeds, ctx.program_id);
+        require_keys_eq!(pool_info.key(), pool_key, ErrorCode::InvalidPda);
+
+        let (vault_key, vault_bump) =
+            Pubkey::find_program_address(&[b"bet_vault", pool_key.as_ref()], ctx.program_id);
+        require_keys_eq!(vault_info.key(), vault_key, ErrorCode::InvalidPda);
+
+        create_pda_account(
+            &admin,
+            pool_info,
+            &system_program,
+            8 + std::mem::size_of::<BetPool>(),
+            &[pool_seeds, &[&[pool_bump]]].concat(),
+        )?;
+        let bet_pool = BetPool::new(
+            fixture,
+            spec,
+            ctx.accounts.fee_vault.key(),
+            &ctx.accounts.config,
+            pool_bump,
+        );
+        bet_pool.try_serialize(&mut &mut pool_info.try_borrow_mut_data()?[..])?;
+
+        create_pda_account(
+            &admin,
+            vault_info,
+            &system_program,
+            8 + std::mem::size_of::<BetVault>(),
+            &[b"bet_vault", pool_key.as_ref(), &[vault_bump]],
+        )?;
+        let bet_vault = BetVault {
+            owner: pool_key,
+            bump: vault_bump,
+            ..Default::default()
+        };
+        bet_vault.try_serialize(&mut &mut vault_info.try_borrow_mut_data()?[..])?;
 
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts,
-        signer,
+        msg!("🧠 Batch pool: {}", pool_key);
+    }
+
+    msg!("📦 Created {} pools for fixture {}", pools.len(), fixture.fixture_id);
+    Ok(())
+}
+
+pub fn create_session(
+    ctx: Context<CreateSession>,
+    session_key: Pubkey,
+    spend_cap: u64,
+    expires_at: i64,
+    allowed_instructions: u8,
+) -> Result<()> {
+    require!(
+        expires_at > Clock::get()?.unix_timestamp
+            && allowed_instructions != 0
+            && allowed_instructions & !SESSION_ALL == 0,
+        ErrorCode::InvalidSessionParams
     );
 
-    token::transfer(cpi_ctx, 1)?;
+    let session = &mut ctx.accounts.session_token;
+    session.owner = ctx.accounts.owner.key();
+    session.session_key = session_key;
+    session.spend_cap = spend_cap;
+    session.spent = 0;
+    session.expires_at = expires_at;
+    session.allowed_instructions = allowed_instructions;
+    session.bump = ctx.bumps.session_token;
 
+    msg!("🔐 Session {} for {} until {}", session_key, session.owner, expires_at);
     Ok(())
 }
 
+// Closing the PDA revokes the session immediately
+pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+    msg!("🔐 Revoked session {}", ctx.accounts.session_token.session_key);
+    Ok(())
 }
 
-//program end 
-#[derive(Accounts)]
-pub struct AdminUpdateResult<'info> {
-    #[account(mut)]
-    pub bet_pool: Account<'info, BetPool>,
-    pub authority: Signer<'info>,
+pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+    require_not_blocked(&ctx.accounts.owner_block)?;
+
+    let balance = &mut ctx.accounts.balance;
+    if balance.owner == Pubkey::default() {
+        balance.owner = ctx.accounts.owner.key();
+        balance.bump = ctx.bumps.balance;
+    }
+    invoke(
+        &system_instruction::transfer(&ctx.accounts.owner.key(), &balance.key(), amount),
+        &[
+            ctx.accounts.owner.to_account_info(),
+            balance.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+    balance.amount += amount;
+    balance.total_deposited += amount;
+
+    msg!("💰 Deposited {} lamports, balance {}", amount, balance.amount);
+    Ok(())
 }
 
-#[derive(Accounts)]
-#[instruction(fixture_id: u64, player_id: Pubkey, stat_name: [u8; 32], stat_line: u32, sport_name: [u8; 32])]
-pub struct InitializeBetPool<'info> {
-    #[account(mut)]
-    pub admin: Signer<'info>,
+pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    require_not_blocked(&ctx.accounts.owner_block)?;
 
-    #[account(
-        init,
-        payer = admin,
-        space = 8 + std::mem::size_of::<BetPool>(),
-        seeds = [
-            b"bet_pool",
-            &fixture_id.to_le_bytes(),
-            &sport_name[..],
-            player_id.as_ref(),
-            &stat_name,
-            &stat_line.to_le_bytes(),
-        ],
-        bump,
-    )]
-    pub bet_pool: Account<'info, BetPool>,
+    let balance = &mut ctx.accounts.balance;
+    require!(balance.amount >= amount, ErrorCode::InsufficientBalance);
+    **balance.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.owner.to_account_info().try_borrow_mut_lamports()? += amount;
+    balance.amount -= amount;
+    balance.total_withdrawn += amount;
 
-#[account(
-    init,
-    payer = admin,
-    space = 8 + 0,
-    seeds = [b"fee_vault", bet_pool.key().as_ref()],
-    bump
-)]
-pub fee_vault: UncheckedAccount<'info>,
+    msg!("💰 Withdrew {} lamports, balance {}", amount, balance.amount);
+    Ok(())
+}
+
+// Anyone may open a profile on a wallet's behalf, e.g. before its first claim
+pub fn initialize_user_profile(ctx: Context<InitializeUserProfile>, wallet: Pubkey) -> Result<()> {
+    ctx.accounts.user_profile.init(wallet, ctx.bumps.user_profile);
+    msg!("👤 Opened profile for {}", wallet);
+    Ok(())
+}
+
+// Tighter limits apply at once; looser ones wait out LIMIT_INCREASE_COOLDOWN
+pub fn set_limits(ctx: Context<SetLimits>, limits: ResponsibleLimits) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let user_profile = &mut ctx.accounts.user_profile;
+    user_profile.init(ctx.accounts.wallet.key(), ctx.bumps.user_profile);
+    user_profile.apply_due_limits(now);
+
+    let current = user_profile.limits;
+    user_profile.limits = current.tightest(&limits);
+    if user_profile.limits == limits {
+        user_profile.pending_limits = ResponsibleLimits::default();
+        user_profile.pending_limits_at = 0;
+    } else {
+        user_profile.pending_limits = limits;
+        user_profile.pending_limits_at = now + LIMIT_INCREASE_COOLDOWN;
+    }
+
+    msg!("🛡️ Limits now {:?}, pending {:?}", user_profile.limits, user_profile.pending_limits);
+    Ok(())
+}
+
+// Exclusion can be extended but never shortened
+pub fn self_exclude(ctx: Context<SetLimits>, duration: i64) -> Result<()> {
+    require!(duration > 0, ErrorCode::InvalidExclusionPeriod);
+
+    let now = Clock::get()?.unix_timestamp;
+    let user_profile = &mut ctx.accounts.user_profile;
+    user_profile.init(ctx.accounts.wallet.key(), ctx.bumps.user_profile);
+    user_profile.self_excluded_until = user_profile.self_excluded_until.max(now + duration);
+
+    msg!("🛡️ {} self-excluded until {}", user_profile.wallet, user_profile.self_excluded_until);
+    Ok(())
+}
+
+/// Stakes `bet_amount` from the bettor (wallet or internal balance) and mints the pick NFT.
+/// Rent for new accounts comes from `payer`, which callers without a sponsor set to the bettor.
+pub fn place_bet(
+    ctx: Context<PlaceBet>,
+    _fixture_id: u64,
+    _player_id: Pubkey,
+    _stat_name: [u8; 32],
+    _stat_line: u32,
+    bet_amount: u64,
+    pick_side: bool,
+    _sport_name: [u8; 32],
+) -> Result<()> {
+    require!(
+        ctx.accounts.fixture.status == FixtureStatus::Scheduled,
+        ErrorCode::FixtureNotScheduled
+    );
+    require!(
+        ctx.accounts.bet_pool.status == PoolStatus::Open
+            && Clock::get()?.unix_timestamp < ctx.accounts.bet_pool.deadline,
+        ErrorCode::BettingClosed
+    );
+    require!(ctx.accounts.player.active, ErrorCode::PlayerInactive);
+    authorize_session(
+        &ctx.accounts.bettor.key(),
+        &ctx.accounts.authority.key(),
+        ctx.accounts.session_token.as_mut(),
+        SESSION_PLACE_BET,
+        bet_amount,
+        Clock::get()?.unix_timestamp,
+    )?;
+    require_not_blocked(&ctx.accounts.bettor_block)?;
+    require_attested(
+        &ctx.accounts.bet_pool,
+        ctx.accounts.attestation.as_ref(),
+        &ctx.accounts.bettor.key(),
+        Clock::get()?.unix_timestamp,
+    )?;
+    require_not_paused(
+        PAUSE_BETTING,
+        &ctx.accounts.config,
+        &ctx.accounts.sport,
+        &ctx.accounts.bet_pool,
+    )?;
+
+    // 1. Save current nonce and increment it
+    let nonce = ctx.accounts.user_nonce.count;
+    ctx.accounts.user_nonce.count += 1;
+
+    let user_pick = &mut ctx.accounts.user_pick;
+    let sport_name = ctx.accounts.bet_pool.sport_name;
+    let bet_pool = &mut ctx.accounts.bet_pool;
+    bet_pool.pick_count += 1;
+
+    let fee = (bet_amount * 5) / 100;
+    let net_amount = bet_amount - fee;
+    bet_pool.total_fees_collected += fee;
+
+    let user_profile = &mut ctx.accounts.user_profile;
+    user_profile.init(ctx.accounts.bettor.key(), ctx.bumps.user_profile);
+    user_profile.record_deposit(bet_amount, Clock::get()?.unix_timestamp)?;
+    user_profile.record_bet(bet_amount, fee);
+
+    user_pick.owner = ctx.accounts.bettor.key();
+    user_pick.bet_amount = net_amount;
+    user_pick.pick_side = pick_side;
+    user_pick.pool = bet_pool.key();
+    user_pick.claimed = false;
+    user_pick.mint = ctx.accounts.mint.key();
+    user_pick.bump = ctx.bumps.user_pick;
+    user_pick.sport_name = sport_name;
+    user_pick.rent_payer = ctx.accounts.payer.key();
+
+    if pick_side {
+        bet_pool.total_over_amount += net_amount;
+    } else {
+        bet_pool.total_under_amount += net_amount;
+    }
+
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let bettor = ctx.accounts.bettor.to_account_info();
+    collect_from(
+        &bettor,
+        ctx.accounts.bettor_balance.as_mut(),
+        &ctx.accounts.fee_vault.to_account_info(),
+        &system_program,
+        fee,
+    )?;
+    collect_from(
+        &bettor,
+        ctx.accounts.bettor_balance.as_mut(),
+        &ctx.accounts.bet_vault.to_account_info(),
+        &system_program,
+        net_amount,
+    )?;
+    ctx.accounts.fee_vault.record_deposit(fee);
+    ctx.accounts.bet_vault.record_deposit(net_amount);
+
+    let mint_authority_bump = ctx.bumps.mint_authority;
+    let mint_key = user_pick.key();
+    let mint_seeds: &[&[u8]] = &[b"mint", mint_key.as_ref(), &[mint_authority_bump]];
+    let signer_seeds: &[&[&[u8]]] = &[mint_seeds];
+
+
+        let cpi_accounts = token::InitializeMint {
+            mint: ctx.accounts.mint.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        if ctx.accounts.mint.to_account_info().try_borrow_data()?[0] == 0 {
+    let cpi_accounts = token::InitializeMint {
+        mint: ctx.accounts.mint.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::initialize_mint(
+        cpi_ctx,
+        0,
+        &ctx.accounts.mint_authority.key(),
+        Some(&ctx.accounts.mint_authority.key()),
+    )?;
+}
+
+    if ctx.accounts.user_token_account.to_account_info().try_borrow_data()?.len() == 0 {
+        let ata_ctx = CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.payer.to_account_info(),
+                associated_token: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.bettor.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        );
+        associated_token::create(ata_ctx)?;
+    }
+
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info(),
+        authority: ctx.accounts.mint_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::mint_to(cpi_ctx, 1)?;
+
+    Ok(())
+}
+
+pub fn lock_bet_pool(ctx: Context<LockBetPool>) -> Result<()> {
+    require_role(&ctx.accounts.authority.key(), ctx.accounts.role_grant.as_ref(), Role::Resolver)?;
+
+    let bet_pool = &mut ctx.accounts.bet_pool;
+    require!(bet_pool.status == PoolStatus::Open, ErrorCode::BettingClosed);
+    bet_pool.status = PoolStatus::Locked;
+
+    msg!("🔐 Locked pool {}", bet_pool.key());
+    Ok(())
+}
+
+pub fn dispute_result(ctx: Context<DisputeResult>, proposed_stat: u32) -> Result<()> {
+    let bet_pool = &mut ctx.accounts.bet_pool;
+    require!(bet_pool.status != PoolStatus::Finalized, ErrorCode::AlreadyPublished);
+    require!(bet_pool.status == PoolStatus::Proposed, ErrorCode::PoolNotProposed);
+    require!(
+        Clock::get()?.unix_timestamp < bet_pool.dispute_window_end,
+        ErrorCode::DisputeWindowClosed
+    );
+    require!(!bet_pool.dispute_open, ErrorCode::DisputeOpen);
+    require!(proposed_stat != bet_pool.final_stat, ErrorCode::DisputeMatchesResult);
+    // A single corrected total can't keep the component breakdown consistent; composite
+    // pools are corrected component by component through the council instead
+    require!(bet_pool.stat_component_count == 0, ErrorCode::CompositeDisputeUnsupported);
+    // An upheld dispute must be able to log its correction
+    require!(
+        (bet_pool.amendment_count as usize) < MAX_STAT_AMENDMENTS,
+        ErrorCode::TooManyAmendments
+    );
+
+    let stat_type = ctx
+        .accounts
+        .sport
+        .stat_type(&bet_pool.stat_name)
+        .ok_or(ErrorCode::InvalidStatType)?;
+    require!(stat_type.is_plausible(proposed_stat), ErrorCode::ImplausibleStatValue);
+
+    let bond = ctx.accounts.config.dispute_bond;
+    invoke(
+        &system_instruction::transfer(
+            &ctx.accounts.disputer.key(),
+            &ctx.accounts.dispute.key(),
+            bond,
+        ),
+        &[
+            ctx.accounts.disputer.to_account_info(),
+            ctx.accounts.dispute.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.pool = bet_pool.key();
+    dispute.disputer = ctx.accounts.disputer.key();
+    dispute.proposed_stat = proposed_stat;
+    dispute.bond = bond;
+    dispute.opened_at = Clock::get()?.unix_timestamp;
+    dispute.bump = ctx.bumps.dispute;
+    bet_pool.dispute_open = true;
+
+    msg!("⚖️ Dispute opened: {} -> {}", bet_pool.final_stat, proposed_stat);
+    Ok(())
+}
+
+pub fn resolve_dispute(ctx: Context<ResolveDispute>, upheld: bool) -> Result<()> {
+    let config = &ctx.accounts.config;
+    require_keys_eq!(ctx.accounts.arbitrator.key(), config.arbitrator, ErrorCode::Unauthorized);
+
+    let bet_pool = &mut ctx.accounts.bet_pool;
+    require!(bet_pool.status != PoolStatus::Finalized, ErrorCode::AlreadyPublished);
+
+    let dispute = ctx.accounts.dispute.to_account_info();
+    let disputer = ctx.accounts.disputer.to_account_info();
+    let insurance_fund = ctx.accounts.insurance_fund.to_account_info();
+
+    // The bond itself is returned when the dispute account closes to the disputer
+    let mut reward = 0;
+    if upheld {
+        // dispute_result only opens disputes while a history slot is free
+        let corrected = ctx.accounts.dispute.proposed_stat;
+        bet_pool.record_amendment(corrected, config.arbitrator, Clock::get()?.unix_timestamp);
+        let rent_floor = Rent::get()?.minimum_balance(insurance_fund.data_len());
+        reward = config
+            .dispute_reward
+            .min(insurance_fund.lamports().saturating_sub(rent_floor));
+        **insurance_fund.try_borrow_mut_lamports()? -= reward;
+        **disputer.try_borrow_mut_lamports()? += reward;
+    } else {
+        let bond = ctx.accounts.dispute.bond;
+        **dispute.try_borrow_mut_lamports()? -= bond;
+        **insurance_fund.try_borrow_mut_lamports()? += bond;
+    }
+    bet_pool.dispute_open = false;
+
+    emit!(DisputeResolved {
+        pool: bet_pool.key(),
+        disputer: disputer.key(),
+        upheld,
+        final_stat: bet_pool.final_stat,
+        reward,
+    });
+    Ok(())
+}
+
+// Permissionless once the arbitrator has missed dispute_timeout: the proposed result
+// stands and the bond goes back to the disputer, so a dispute can't freeze the pool
+pub fn expire_dispute(ctx: Context<ExpireDispute>) -> Result<()> {
+    let dispute = &ctx.accounts.dispute;
+    require!(
+        Clock::get()?.unix_timestamp >= dispute.opened_at + ctx.accounts.config.dispute_timeout,
+        ErrorCode::DisputePending
+    );
+    ctx.accounts.bet_pool.dispute_open = false;
+
+    msg!("⚖️ Dispute on {} lapsed, bond returned to {}", dispute.pool, dispute.disputer);
+    Ok(())
+}
+
+// Permissionless: anyone can finalize once the dispute window has elapsed
+pub fn finalize_result(ctx: Context<FinalizeResult>) -> Result<()> {
+    let bet_pool = &mut ctx.accounts.bet_pool;
+    require!(bet_pool.status != PoolStatus::Finalized, ErrorCode::AlreadyPublished);
+    require!(bet_pool.status == PoolStatus::Proposed, ErrorCode::PoolNotProposed);
+
+    // Postponed pools settle as refunds; finalizing now would mix refunds with winnings
+    require!(
+        ctx.accounts.fixture.status != FixtureStatus::Postponed,
+        ErrorCode::FixturePostponed
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= bet_pool.dispute_window_end, ErrorCode::DisputeWindowOpen);
+    require!(!bet_pool.dispute_open, ErrorCode::DisputeOpen);
+    // Payouts are held until late stat corrections can no longer happen
+    require!(now >= bet_pool.correction_window_end(), ErrorCode::CorrectionWindowOpen);
+
+    bet_pool.status = PoolStatus::Finalized;
+    bet_pool.claim_deadline = now + ctx.accounts.config.claim_period;
+
+    emit!(ResultFinalized {
+        pool: bet_pool.key(),
+        final_stat: bet_pool.final_stat,
+        claim_deadline: bet_pool.claim_deadline,
+    });
+    Ok(())
+}
+
+pub fn set_pool_compliance(
+    ctx: Context<ConfigureBetPool>,
+    requires_attestation: bool,
+    jurisdictions: Vec<[u8; 2]>,
+) -> Result<()> {
+    require_role(&ctx.accounts.authority.key(), ctx.accounts.role_grant.as_ref(), Role::Compliance)?;
+    require!(jurisdictions.len() <= MAX_JURISDICTIONS, ErrorCode::TooManyJurisdictions);
+
+    let bet_pool = &mut ctx.accounts.bet_pool;
+    bet_pool.requires_attestation = requires_attestation;
+    bet_pool.allowed_jurisdictions = [[0; 2]; MAX_JURISDICTIONS];
+    bet_pool.allowed_jurisdictions[..jurisdictions.len()].copy_from_slice(&jurisdictions);
+    bet_pool.jurisdiction_count = jurisdictions.len() as u8;
+
+    msg!("🪪 Attestation required: {} ({} jurisdictions)", requires_attestation, jurisdictions.len());
+    Ok(())
+}
+
+pub fn set_stat_components(
+    ctx: Context<ConfigureBetPool>,
+    components: Vec<StatComponent>,
+) -> Result<()> {
+    require_role(&ctx.accounts.authority.key(), ctx.accounts.role_grant.as_ref(), Role::PoolCreator)?;
+
+    let bet_pool = &mut ctx.accounts.bet_pool;
+    require!(bet_pool.status == PoolStatus::Open, ErrorCode::AlreadyPublished);
+    require!(
+        bet_pool.total_over_amount == 0 && bet_pool.total_under_amount == 0,
+        ErrorCode::PoolAlreadyActive
+    );
+    require!(
+        !components.is_empty() && components.len() <= MAX_STAT_COMPONENTS,
+        ErrorCode::InvalidStatComponents
+    );
+    require!(
+        components.iter().all(|c| c.weight > 0),
+        ErrorCode::InvalidStatComponents
+    );
+    require!(
+        components
+            .iter()
+            .all(|c| ctx.accounts.sport.stat_type(&c.stat_name).is_some()),
+        ErrorCode::InvalidStatType
+    );
+
+    bet_pool.stat_components = [StatComponent::default(); MAX_STAT_COMPONENTS];
+    bet_pool.stat_components[..components.len()].copy_from_slice(&components);
+    bet_pool.stat_component_count = components.len() as u8;
+
+    msg!("🧩 Composite prop with {} components", components.len());
+    Ok(())
+}
+
+pub fn settle_claim(ctx: Context<SettleClaim>) -> Result<()> {
+    authorize_session(
+        &ctx.accounts.recipient.key(),
+        &ctx.accounts.authority.key(),
+        ctx.accounts.session_token.as_mut(),
+        SESSION_SETTLE_CLAIM,
+        0,
+        Clock::get()?.unix_timestamp,
+    )?;
+    require_not_paused(
+        PAUSE_CLAIMS,
+        &ctx.accounts.config,
+        &ctx.accounts.sport,
+        &ctx.accounts.bet_pool,
+    )?;
+    let voided = ctx.accounts.fixture.status == FixtureStatus::Postponed;
+    let settlement = settle_pick(
+        &mut ctx.accounts.bet_pool,
+        &mut ctx.accounts.user_pick,
+        voided,
+        ctx.accounts.config.claim_period,
+    )?;
+    match settlement {
+        Settlement::Refund(amount) => {
+            msg!("💸 Refunding {} lamports to {}", amount, ctx.accounts.recipient.key());
+        }
+        Settlement::Won(amount) => {
+            msg!("🏆 Paying {} lamports to {}", amount, ctx.accounts.recipient.key());
+        }
+        Settlement::Lost | Settlement::Push => {}
+    }
+    let recipient_profile = &mut ctx.accounts.recipient_profile;
+    recipient_profile.init(ctx.accounts.recipient.key(), ctx.bumps.recipient_profile);
+    recipient_profile.record_settlement(settlement, Clock::get()?.unix_timestamp);
+
+    // Winnings roll into the holder's internal balance when one is supplied
+    let pool_key = ctx.accounts.bet_pool.key();
+    match ctx.accounts.recipient_balance.as_mut() {
+        Some(balance) if ctx.accounts.recipient_block.data_is_empty() => {
+            require_keys_eq!(balance.owner, ctx.accounts.recipient.key(), ErrorCode::BalanceMismatch);
+            withdraw_from_vault(
+                &mut ctx.accounts.bet_vault,
+                &[pool_key],
+                &balance.to_account_info(),
+                settlement.payout(),
+            )?;
+            balance.amount += settlement.payout();
+        }
+        _ => {
+            pay_or_hold(
+                &mut ctx.accounts.bet_vault,
+                pool_key,
+                &ctx.accounts.recipient.to_account_info(),
+                &ctx.accounts.recipient_block.to_account_info(),
+                settlement.payout(),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn crank_settle<'info>(ctx: Context<'_, '_, 'info, 'info, CrankSettle<'info>>) -> Result<()> {
+    // Each pick supplies [user_pick, holder_token_account, holder, holder_block, holder_profile]
+    require!(!ctx.remaining_accounts.is_empty(), ErrorCode::EmptyCrank);
+    require!(
+        ctx.remaining_accounts.chunks_exact(5).remainder().is_empty(),
+        ErrorCode::BatchAccountsMismatch
+    );
+    require_not_paused(
+        PAUSE_CLAIMS,
+        &ctx.accounts.config,
+        &ctx.accounts.sport,
+        &ctx.accounts.bet_pool,
+    )?;
+
+    let voided = ctx.accounts.fixture.status == FixtureStatus::Postponed;
+    let claim_period = ctx.accounts.config.claim_period;
+    let pool_key = ctx.accounts.bet_pool.key();
+    let fee_vault_owners = [pool_key, ctx.accounts.bet_pool.fixture];
+    let mut settled = 0u64;
+    let mut paid_out = 0u64;
+
+    let cranker = ctx.accounts.cranker.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let now = Clock::get()?.unix_timestamp;
+
+    for accounts in ctx.remaining_accounts.chunks(5) {
+        let (pick_info, token_info, holder_info, block_info, profile_info) =
+            (&accounts[0], &accounts[1], &accounts[2], &accounts[3], &accounts[4]);
+
+        let mut user_pick: Account<UserPick> = Account::try_from(pick_info)?;
+        if user_pick.claimed {
+            continue;
+        }
+
+        // Listed picks sit in escrow; their seller settles after reclaiming
+        let (escrow_pda, _) =
+            Pubkey::find_program_address(&[b"escrow", pick_info.key.as_ref()], ctx.program_id);
+        if holder_info.key() == escrow_pda {
+            continue;
+        }
+
+        let holder_token_account: Account<TokenAccount> = Account::try_from(token_info)?;
+        require_keys_eq!(holder_token_account.mint, user_pick.mint, ErrorCode::NotPickHolder);
+        require_keys_eq!(holder_token_account.owner, holder_info.key(), ErrorCode::NotPickHolder);
+        require!(holder_token_account.amount == 1, ErrorCode::NotPickHolder);
+        let (holder_block, _) =
+            Pubkey::find_program_address(&[b"blocked", holder_info.key.as_ref()], ctx.program_id);
+        require_keys_eq!(block_info.key(), holder_block, ErrorCode::InvalidBlockAccount);
+
+        let (profile_key, profile_bump) =
+            Pubkey::find_program_address(&[b"user_profile", holder_info.key.as_ref()], ctx.program_id);
+        require_keys_eq!(profile_info.key(), profile_key, ErrorCode::ProfileMismatch);
+        // The cranker covers rent for holders who never opened a profile
+        if profile_info.data_is_empty() {
+            create_pda_account(
+                &cranker,
+                profile_info,
+                &system_program,
+                8 + std::mem::size_of::<UserProfile>(),
+                &[b"user_profile", holder_info.key.as_ref(), &[profile_bump]],
+            )?;
+            let profile = UserProfile {
+                wallet: holder_info.key(),
+                bump: profile_bump,
+                ..Default::default()
+            };
+            profile.try_serialize(&mut &mut profile_info.try_borrow_mut_data()?[..])?;
+        }
+        let mut holder_profile: Account<UserProfile> = Account::try_from(profile_info)?;
+
+        let settlement = settle_pick(&mut ctx.accounts.bet_pool, &mut user_pick, voided, claim_period)?;
+        holder_profile.record_settlement(settlement, now);
+        holder_profile.exit(ctx.program_id)?;
+        let payout = settlement.payout();
+        pay_or_hold(&mut ctx.accounts.bet_vault, pool_key, holder_info, block_info, payout)?;
+        user_pick.exit(ctx.program_id)?;
+
+        settled += 1;
+        paid_out += payout;
+    }
+
+    // Keeper tip comes out of collected fees, never below the fee vault's rent floor
+    let tip = ctx
+        .accounts
+        .config
+        .crank_tip
+        .saturating_mul(settled)
+        .min(vault_excess(&ctx.accounts.fee_vault)?);
+    withdraw_from_vault(
+        &mut ctx.accounts.fee_vault,
+        &fee_vault_owners,
+        &ctx.accounts.cranker.to_account_info(),
+        tip,
+    )?;
+    ctx.accounts.bet_pool.total_fees_disbursed += tip;
+
+    emit!(PicksCranked {
+        pool: ctx.accounts.bet_pool.key(),
+        settled,
+        paid_out,
+        tip,
+    });
+    Ok(())
+}
+
+// Lets holders recover their stake if no result arrives before deadline + resolution_timeout
+pub fn emergency_refund(ctx: Context<EmergencyRefund>) -> Result<()> {
+    let bet_pool = &mut ctx.accounts.pool;
+    let user_pick = &mut ctx.accounts.user_pick;
+
+    require!(!user_pick.claimed, ErrorCode::AlreadyClaimed);
+    // Lamports sent to the escrow PDA could never leave it; the seller reclaims first
+    let (escrow_pda, _) =
+        Pubkey::find_program_address(&[b"escrow", user_pick.key().as_ref()], ctx.program_id);
+    require_keys_neq!(ctx.accounts.holder.key(), escrow_pda, ErrorCode::PickListed);
+    let now = Clock::get()?.unix_timestamp;
+    require!(bet_pool.resolution_timed_out(now), ErrorCode::ResolutionPending);
+    require!(
+        now < bet_pool.effective_claim_deadline(ctx.accounts.config.claim_period),
+        ErrorCode::ClaimDeadlinePassed
+    );
+
+    let refund = user_pick.bet_amount;
+    msg!("🆘 Emergency refund of {} lamports to {}", refund, ctx.accounts.holder.key());
+
+    bet_pool.record_payout(refund)?;
+    let holder_profile = &mut ctx.accounts.holder_profile;
+    holder_profile.init(ctx.accounts.holder.key(), ctx.bumps.holder_profile);
+    holder_profile.record_settlement(Settlement::Refund(refund), now);
+    pay_or_hold(
+        &mut ctx.accounts.bet_vault,
+        bet_pool.key(),
+        &ctx.accounts.holder.to_account_info(),
+        &ctx.accounts.holder_block.to_account_info(),
+        refund,
+    )?;
+
+    user_pick.claimed = true;
+    bet_pool.settled_count += 1;
+    Ok(())
+}
+
+pub fn sweep_unclaimed(ctx: Context<SweepUnclaimed>) -> Result<()> {
+    let bet_pool = &mut ctx.accounts.bet_pool;
+    let voided = ctx.accounts.fixture.status == FixtureStatus::Postponed;
+    let timed_out = bet_pool.resolution_timed_out(Clock::get()?.unix_timestamp);
+    require!(
+        bet_pool.status == PoolStatus::Finalized || voided || timed_out,
+        ErrorCode::PoolNotSettled
+    );
+    let claim_deadline = bet_pool.effective_claim_deadline(ctx.accounts.config.claim_period);
+    require!(
+        Clock::get()?.unix_timestamp >= claim_deadline,
+        ErrorCode::ClaimPeriodActive
+    );
+
+    let (jackpot_vault, _) = Pubkey::find_program_address(&[b"jackpot_vault"], ctx.program_id);
+    let expected_destination = match ctx.accounts.config.unclaimed_destination {
+        UnclaimedDestination::Treasury => ctx.accounts.config.treasury,
+        UnclaimedDestination::Jackpot => jackpot_vault,
+    };
+    require_keys_eq!(
+        ctx.accounts.destination.key(),
+        expected_destination,
+        ErrorCode::InvalidUnclaimedDestination
+    );
+
+    let amount = vault_excess(&ctx.accounts.bet_vault)?;
+    withdraw_from_vault(
+        &mut ctx.accounts.bet_vault,
+        &[bet_pool.key()],
+        &ctx.accounts.destination.to_account_info(),
+        amount,
+    )?;
+    bet_pool.unclaimed_swept += amount;
+
+    emit!(UnclaimedSwept {
+        pool: bet_pool.key(),
+        destination: expected_destination,
+        amount,
+        unsettled_picks: bet_pool.pick_count - bet_pool.settled_count,
+    });
+    msg!("🧺 Swept {} unclaimed lamports", amount);
+    Ok(())
+}
+
+// Read-only proof of reserves: compares vault balances against the pool's own ledger
+pub fn audit_pool(ctx: Context<AuditPool>) -> Result<AuditReport> {
+    let bet_pool = &ctx.accounts.bet_pool;
+
+    let bet_vault_actual = vault_excess(&ctx.accounts.bet_vault)?;
+    let bet_vault_expected = bet_pool
+        .total_pool()
+        .saturating_sub(bet_pool.total_paid_out)
+        .saturating_sub(bet_pool.unclaimed_swept);
+
+    // Shared fixture fee vaults are reconciled against the vault's own ledger instead
+    let fee_vault = &ctx.accounts.fee_vault;
+    let fee_vault_shared = fee_vault.owner != bet_pool.key();
+    let fee_vault_actual = vault_excess(fee_vault)?;
+    let fee_vault_expected = if fee_vault_shared {
+        fee_vault.balance()
+    } else {
+        bet_pool
+            .total_fees_collected
+            .saturating_sub(bet_pool.total_fees_disbursed)
+    };
+
+    let report = AuditReport {
+        pool: bet_pool.key(),
+        bet_vault_expected,
+        bet_vault_actual,
+        bet_vault_discrepancy: signed_difference(bet_vault_actual, bet_vault_expected),
+        fee_vault_shared,
+        fee_vault_expected,
+        fee_vault_actual,
+        fee_vault_discrepancy: signed_difference(fee_vault_actual, fee_vault_expected),
+        solvent: bet_vault_actual >= bet_vault_expected && fee_vault_actual >= fee_vault_expected,
+    };
+
+    emit!(PoolAudited { report: report.clone() });
+    if !report.solvent {
+        msg!("🚨 Pool {} is under-reserved", report.pool);
+    }
+    Ok(report)
+}
+
+pub fn close_bet_pool(ctx: Context<CloseBetPool>) -> Result<()> {
+    require_role(&ctx.accounts.admin.key(), ctx.accounts.role_grant.as_ref(), Role::Treasurer)?;
+
+    let bet_pool = &ctx.accounts.bet_pool;
+    let voided = ctx.accounts.fixture.status == FixtureStatus::Postponed;
+    let timed_out = bet_pool.resolution_timed_out(Clock::get()?.unix_timestamp);
+    require!(
+        bet_pool.status == PoolStatus::Finalized || voided || timed_out,
+        ErrorCode::PoolNotSettled
+    );
+
+    let all_settled = bet_pool.settled_count == bet_pool.pick_count;
+    let claims_expired = Clock::get()?.unix_timestamp
+        >= bet_pool.effective_claim_deadline(ctx.accounts.config.claim_period);
+    require!(all_settled || claims_expired, ErrorCode::PoolNotClosable);
+    // Forfeited stakes follow config.unclaimed_destination via sweep_unclaimed, not the treasury
+    require!(
+        all_settled || vault_excess(&ctx.accounts.bet_vault)? == 0,
+        ErrorCode::UnclaimedNotSwept
+    );
+
+    let pool_key = bet_pool.key();
+    let treasury = ctx.accounts.treasury.to_account_info();
+    let swept = vault_excess(&ctx.accounts.bet_vault)?;
+    withdraw_from_vault(&mut ctx.accounts.bet_vault, &[pool_key], &treasury, swept)?;
+
+    // Batch pools share a fixture-level fee vault which must outlive this pool. Fees only
+    // leave through the council, so a pool's own vault must be emptied before it closes.
+    if ctx.accounts.fee_vault.owner == pool_key {
+        require!(vault_excess(&ctx.accounts.fee_vault)? == 0, ErrorCode::FeesNotWithdrawn);
+        ctx.accounts.fee_vault.close(ctx.accounts.admin.to_account_info())?;
+    }
+
+    msg!("🧹 Closed pool {}: swept {} stake", pool_key, swept);
+    Ok(())
+}
+
+
+pub fn initialize_council(
+    ctx: Context<InitializeCouncil>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require_admin(&ctx.accounts.admin.key())?;
+    validate_council(&members, threshold)?;
+
+    let council = &mut ctx.accounts.council;
+    council.set_members(&members, threshold);
+    council.bump = ctx.bumps.council;
+
+    msg!("🏛️ Council of {} with threshold {}", members.len(), threshold);
+    Ok(())
+}
+
+pub fn propose_action(ctx: Context<ProposeAction>, action: CouncilAction) -> Result<()> {
+    let council = &mut ctx.accounts.council;
+    let member = council
+        .member_index(&ctx.accounts.proposer.key())
+        .ok_or(ErrorCode::NotCouncilMember)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.council = council.key();
+    proposal.index = council.proposal_count;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.epoch = council.epoch;
+    proposal.action = action;
+    proposal.approvals = 1 << member;
+    proposal.approval_count = 1;
+    proposal.executed = false;
+    proposal.created_at = Clock::get()?.unix_timestamp;
+    proposal.bump = ctx.bumps.proposal;
+    council.proposal_count += 1;
+
+    msg!("🏛️ Proposal {} opened: {:?}", proposal.index, proposal.action);
+    Ok(())
+}
+
+pub fn approve_action(ctx: Context<ApproveAction>) -> Result<()> {
+    let member = ctx
+        .accounts
+        .council
+        .member_index(&ctx.accounts.member.key())
+        .ok_or(ErrorCode::NotCouncilMember)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    require!(!proposal.executed, ErrorCode::ProposalExecuted);
+    require!(proposal.epoch == ctx.accounts.council.epoch, ErrorCode::ProposalStale);
+    require!(proposal.approvals & (1 << member) == 0, ErrorCode::AlreadyApproved);
+    proposal.approvals |= 1 << member;
+    proposal.approval_count += 1;
+
+    msg!(
+        "🏛️ Proposal {} approved ({}/{})",
+        proposal.index,
+        proposal.approval_count,
+        ctx.accounts.council.threshold
+    );
+    Ok(())
+}
+
+// The only path for publishing results, withdrawing fees or jackpot funds, changing
+// config (royalty included) or rotating the council. Permissionless once the threshold is met; unused accounts may be omitted.
+pub fn execute_action(ctx: Context<ExecuteAction>) -> Result<()> {
+    let council_key = ctx.accounts.council.key();
+    let proposal = &mut ctx.accounts.proposal;
+    require!(!proposal.executed, ErrorCode::ProposalExecuted);
+    require!(proposal.epoch == ctx.accounts.council.epoch, ErrorCode::ProposalStale);
+    require!(
+        proposal.approval_count >= ctx.accounts.council.threshold,
+        ErrorCode::InsufficientApprovals
+    );
+
+    match proposal.action {
+        CouncilAction::PublishResult { bet_pool, final_stat } => {
+            let (Some(pool), Some(fixture), Some(sport)) = (
+                ctx.accounts.bet_pool.as_mut(),
+                ctx.accounts.fixture.as_ref(),
+                ctx.accounts.sport.as_ref(),
+            ) else {
+                return err!(ErrorCode::ActionAccountsMismatch);
+            };
+            require_keys_eq!(pool.key(), bet_pool, ErrorCode::ActionAccountsMismatch);
+            require_keys_eq!(pool.fixture, fixture.key(), ErrorCode::FixtureMismatch);
+            require!(sport.sport_name == pool.sport_name, ErrorCode::ActionAccountsMismatch);
+
+            publish_stat(
+                pool,
+                fixture,
+                sport,
+                final_stat,
+                council_key,
+                ctx.accounts.config.dispute_window,
+            )?;
+            msg!("📝 Proposed final stat: {}", final_stat);
+        }
+        CouncilAction::PublishCompositeResult { bet_pool, component_values } => {
+            let (Some(pool), Some(fixture), Some(sport)) = (
+                ctx.accounts.bet_pool.as_mut(),
+                ctx.accounts.fixture.as_ref(),
+                ctx.accounts.sport.as_ref(),
+            ) else {
+                return err!(ErrorCode::ActionAccountsMismatch);
+            };
+            require_keys_eq!(pool.key(), bet_pool, ErrorCode::ActionAccountsMismatch);
+            require_keys_eq!(pool.fixture, fixture.key(), ErrorCode::FixtureMismatch);
+            require!(sport.sport_name == pool.sport_name, ErrorCode::ActionAccountsMismatch);
+
+            let count = pool.stat_component_count as usize;
+            let final_stat = publish_composite_stat(
+                pool,
+                fixture,
+                sport,
+                &component_values[..count],
+                council_key,
+                ctx.accounts.config.dispute_window,
+            )?;
+            msg!("🧮 Composite final stat: {}", final_stat);
+        }
+        CouncilAction::WithdrawFees { bet_pool, amount } => {
+            let (Some(pool), Some(fee_vault), Some(treasury)) = (
+                ctx.accounts.bet_pool.as_mut(),
+                ctx.accounts.fee_vault.as_mut(),
+                ctx.accounts.treasury.as_ref(),
+            ) else {
+                return err!(ErrorCode::ActionAccountsMismatch);
+            };
+            require_keys_eq!(pool.key(), bet_pool, ErrorCode::ActionAccountsMismatch);
+            require_keys_eq!(pool.fee_vault, fee_vault.key(), ErrorCode::InvalidFeeVault);
+            require_keys_eq!(treasury.key(), ctx.accounts.config.treasury, ErrorCode::InvalidTreasury);
+
+            withdraw_from_vault(
+                fee_vault,
+                &[pool.key(), pool.fixture],
+                &treasury.to_account_info(),
+                amount,
+            )?;
+            pool.total_fees_disbursed += amount;
+            msg!("🏦 Withdrew {} lamports of fees to treasury", amount);
+        }
+        CouncilAction::WithdrawJackpot { recipient, amount } => {
+            let (Some(jackpot_vault), Some(destination)) = (
+                ctx.accounts.jackpot_vault.as_ref(),
+                ctx.accounts.recipient.as_ref(),
+            ) else {
+                return err!(ErrorCode::ActionAccountsMismatch);
+            };
+            let (expected_vault, _) = Pubkey::find_program_address(&[b"jackpot_vault"], ctx.program_id);
+            require_keys_eq!(jackpot_vault.key(), expected_vault, ErrorCode::InvalidPda);
+            require_keys_eq!(destination.key(), recipient, ErrorCode::ActionAccountsMismatch);
+
+            let vault_info = jackpot_vault.to_account_info();
+            let rent_floor = Rent::get()?.minimum_balance(vault_info.data_len());
+            require!(
+                vault_info.lamports().saturating_sub(rent_floor) >= amount,
+                ErrorCode::VaultOverdrawn
+            );
+            **vault_info.try_borrow_mut_lamports()? -= amount;
+            **destination.to_account_info().try_borrow_mut_lamports()? += amount;
+            msg!("🎰 Paid {} lamports from the jackpot to {}", amount, recipient);
+        }
+        CouncilAction::UpdateConfig(params) => {
+            validate_config_params(&params)?;
+            ctx.accounts.config.apply(&params);
+            msg!("⚙️ Treasury set to {}", params.treasury);
+        }
+        CouncilAction::SetRoyalty { royalty_bps } => {
+            require!(royalty_bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidRoyalty);
+            ctx.accounts.config.royalty_bps = royalty_bps;
+            msg!("🏷️ Marketplace royalty set to {} bps", royalty_bps);
+        }
+        CouncilAction::UpdateCouncil { members, member_count, threshold } => {
+            let members = members
+                .get(..member_count as usize)
+                .ok_or(ErrorCode::InvalidCouncil)?;
+            validate_council(members, threshold)?;
+            // Bumping the epoch invalidates every proposal opened under the old membership
+            let council = &mut ctx.accounts.council;
+            council.set_members(members, threshold);
+            council.epoch += 1;
+            msg!("🏛️ Council rotated to {} with threshold {}", members.len(), threshold);
+        }
+    }
+    proposal.executed = true;
+
+    msg!("🏛️ Executed proposal {}", proposal.index);
+    Ok(())
+}
+
+// Permissionless once settled; the pick's rent goes back to whoever paid it
+pub fn close_user_pick(ctx: Context<CloseUserPick>) -> Result<()> {
+    require!(ctx.accounts.user_pick.claimed, ErrorCode::PoolNotSettled);
+    msg!("🧹 Closed pick {}, rent to {}", ctx.accounts.user_pick.key(), ctx.accounts.rent_payer.key());
+    Ok(())
+}
+
+pub fn delist_pick(ctx: Context<DelistPick>) -> Result<()> {
+let user_pick_key = ctx.accounts.user_pick.key(); // <-- Immutable borrow first
+
+let bump = ctx.bumps.escrow_pda;
+
+let seeds: &[&[u8]] = &[
+    b"escrow",
+    user_pick_key.as_ref(), // use the key from above
+    &[bump],
+];
+let signer = &[seeds];
+
+let cpi_accounts = Transfer {
+    from: ctx.accounts.escrow_token_account.to_account_info(),
+    to: ctx.accounts.seller_token_account.to_account_info(),
+    authority: ctx.accounts.escrow_pda.to_account_info(),
+};
+
+let cpi_ctx = CpiContext::new_with_signer(
+    ctx.accounts.token_program.to_account_info(),
+    cpi_accounts,
+    signer,
+);
+
+token::transfer(cpi_ctx, 1)?;
+
+// Now can mutably borrow `user_pick`
+let user_pick = &mut ctx.accounts.user_pick;
+user_pick.for_sale = false;
+
+Ok(())
+}
+
+
+
+pub fn buy_pick_nft(ctx: Context<BuyPickNFT>, sale_price: u64) -> Result<()> {
+    // Both sides update a profile, so the two wallets must not alias
+    require_keys_neq!(ctx.accounts.buyer.key(), ctx.accounts.seller.key(), ErrorCode::SelfPurchase);
+    // Debiting and crediting the same ledger would mint the sale price out of thin air
+    if let (Some(buyer_balance), Some(seller_balance)) =
+        (ctx.accounts.buyer_balance.as_ref(), ctx.accounts.seller_balance.as_ref())
+    {
+        require_keys_neq!(buyer_balance.key(), seller_balance.key(), ErrorCode::DuplicateBalance);
+    }
+    let user_pick = &mut ctx.accounts.user_pick;
+
+    require!(user_pick.for_sale, ErrorCode::NotListedForSale);
+    require!(!user_pick.claimed, ErrorCode::AlreadyClaimed);
+    require!(!ctx.accounts.pool.status.has_result(), ErrorCode::PoolNotSettled);
+    require_not_paused(
+        PAUSE_MARKETPLACE,
+        &ctx.accounts.config,
+        &ctx.accounts.sport,
+        &ctx.accounts.pool,
+    )?;
+    require_attested(
+        &ctx.accounts.pool,
+        ctx.accounts.attestation.as_ref(),
+        &ctx.accounts.buyer.key(),
+        Clock::get()?.unix_timestamp,
+    )?;
+    require_not_blocked(&ctx.accounts.buyer_block)?;
+    require_not_blocked(&ctx.accounts.seller_block)?;
+
+    let royalty_fee = sale_price * ctx.accounts.config.royalty_bps as u64 / BPS_DENOMINATOR;
+    let seller_amount = sale_price - royalty_fee;
+    ctx.accounts.pool.total_fees_collected += royalty_fee;
+    ctx.accounts.royalty_vault.record_deposit(royalty_fee);
+
+    let buyer_profile = &mut ctx.accounts.buyer_profile;
+    buyer_profile.init(ctx.accounts.buyer.key(), ctx.bumps.buyer_profile);
+    buyer_profile.record_deposit(sale_price, Clock::get()?.unix_timestamp)?;
+    buyer_profile.total_purchased += sale_price;
+    let seller_profile = &mut ctx.accounts.seller_profile;
+    seller_profile.init(ctx.accounts.seller.key(), ctx.bumps.seller_profile);
+    seller_profile.record_sale(seller_amount, royalty_fee, Clock::get()?.unix_timestamp);
+
+    // Pay seller, into their internal balance if they supplied one
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let seller_destination = match ctx.accounts.seller_balance.as_mut() {
+        Some(balance) => {
+            require_keys_eq!(balance.owner, ctx.accounts.seller.key(), ErrorCode::BalanceMismatch);
+            balance.amount += seller_amount;
+            balance.to_account_info()
+        }
+        None => ctx.accounts.seller.to_account_info(),
+    };
+    let buyer = ctx.accounts.buyer.to_account_info();
+    collect_from(
+        &buyer,
+        ctx.accounts.buyer_balance.as_mut(),
+        &seller_destination,
+        &system_program,
+        seller_amount,
+    )?;
+
+    // Pay royalty
+    collect_from(
+        &buyer,
+        ctx.accounts.buyer_balance.as_mut(),
+        &ctx.accounts.royalty_vault.to_account_info(),
+        &system_program,
+        royalty_fee,
+    )?;
+
+    // Derive signer seeds
+    let user_pick_key = user_pick.key();
+    let seeds: &[&[u8]] = &[
+        b"escrow",
+        user_pick_key.as_ref(),
+        // Note: you must use the actual bump here if you don't use #[account(..., bump)]
+        &[Pubkey::find_program_address(&[b"escrow", user_pick_key.as_ref()], ctx.program_id).1],
+    ];
+    let signer = &[seeds]; // type: &[&[&[u8]]]
+
+    // Define CPI accounts
+    let cpi_accounts = anchor_spl::token::Transfer {
+        from: ctx.accounts.escrow_token_account.to_account_info(),
+        to: ctx.accounts.buyer_token_account.to_account_info(),
+        authority: ctx.accounts.escrow_pda.to_account_info(),
+    };
+
+    // Create CPI context with signer
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    );
+
+    // Perform NFT transfer from escrow to buyer
+    token::transfer(cpi_ctx, 1)?;
+
+    // Update on-chain metadata
+    user_pick.owner = ctx.accounts.buyer.key();
+    user_pick.for_sale = false;
+
+    Ok(())
+}
+
+
+        pub fn list_pick_nft(ctx: Context<ListPickNFT>) -> Result<()> {
+        require_not_paused(
+            PAUSE_MARKETPLACE,
+            &ctx.accounts.config,
+            &ctx.accounts.sport,
+            &ctx.accounts.pool,
+        )?;
+        require_not_blocked(&ctx.accounts.seller_block)?;
+        require!(!ctx.accounts.user_pick.for_sale, ErrorCode::AlreadyListed);
+
+        // Transfer NFT from seller to escrow
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.seller_token_account.to_account_info(),
+            to: ctx.accounts.escrow_token_account.to_account_info(),
+            authority: ctx.accounts.seller.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, 1)?;
+        // Buyers pay whoever actually listed, which may differ from the original bettor
+        ctx.accounts.user_pick.owner = ctx.accounts.seller.key();
+        ctx.accounts.user_pick.for_sale = true;
+        Ok(())
+    }
+
+pub fn reclaim_unsold_pick(ctx: Context<ReclaimUnsoldPick>) -> Result<()> {
+    let user_pick = &mut ctx.accounts.user_pick;
+    user_pick.for_sale = false;
+
+    let user_pick_key = user_pick.key();
+    let bump = ctx.bumps.escrow_pda;
+    let seeds: [&[u8]; 3] = [b"escrow", user_pick_key.as_ref(), &[bump]];
+    let signer: &[&[&[u8]]] = &[&seeds];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.escrow_token_account.to_account_info(),
+        to: ctx.accounts.seller_token_account.to_account_info(),
+        authority: ctx.accounts.escrow_pda.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    );
+
+    token::transfer(cpi_ctx, 1)?;
+
+    Ok(())
+}
+
+}
+
+//program end 
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + std::mem::size_of::<Config>(),
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeJackpotVault<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8,
+        seeds = [b"jackpot_vault"],
+        bump
+    )]
+    pub jackpot_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeInsuranceFund<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8,
+        seeds = [b"insurance_fund"],
+        bump
+    )]
+    pub insurance_fund: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(role: Role, grantee: Pubkey)]
+pub struct GrantRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + std::mem::size_of::<RoleGrant>(),
+        seeds = [b"role".as_ref(), &[role as u8], grantee.as_ref()],
+        bump,
+    )]
+    pub role_grant: Account<'info, RoleGrant>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut, close = admin)]
+    pub role_grant: Account<'info, RoleGrant>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct IssueAttestation<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<Attestation>(),
+        seeds = [b"attestation", wallet.as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAttestation<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+
+    #[account(mut, close = authority)]
+    pub attestation: Account<'info, Attestation>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct BlockWallet<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<BlockedWallet>(),
+        seeds = [b"blocked", wallet.as_ref()],
+        bump
+    )]
+    pub blocked_wallet: Account<'info, BlockedWallet>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnblockWallet<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+
+    #[account(mut, address = blocked_wallet.wallet @ ErrorCode::InvalidBlockAccount)]
+    pub wallet: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"blocked", wallet.key().as_ref()],
+        bump = blocked_wallet.bump
+    )]
+    pub blocked_wallet: Account<'info, BlockedWallet>,
+}
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    pub authority: Signer<'info>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub sport: Option<Box<Account<'info, Sport>>>,
+
+    #[account(mut)]
+    pub bet_pool: Option<Account<'info, BetPool>>,
+}
+
+#[derive(Accounts)]
+#[instruction(sport_name: [u8; 32])]
+pub struct InitializeSport<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + std::mem::size_of::<Sport>(),
+        seeds = [b"sport".as_ref(), &sport_name[..]],
+        bump,
+    )]
+    pub sport: Box<Account<'info, Sport>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateSport<'info> {
+    #[account(mut)]
+    pub sport: Box<Account<'info, Sport>>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(fixture_id: u64, sport_name: [u8; 32])]
+pub struct InitializeFixture<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+
+    #[account(seeds = [b"sport".as_ref(), &sport_name[..]], bump = sport.bump)]
+    pub sport: Box<Account<'info, Sport>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + std::mem::size_of::<Fixture>(),
+        seeds = [b"fixture".as_ref(), &fixture_id.to_le_bytes()],
+        bump,
+    )]
+    pub fixture: Account<'info, Fixture>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFixture<'info> {
+    #[account(mut)]
+    pub fixture: Account<'info, Fixture>,
+    pub authority: Signer<'info>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+}
+
+#[derive(Accounts)]
+#[instruction(sport_name: [u8; 32], external_id: u64)]
+pub struct RegisterPlayer<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+
+    #[account(seeds = [b"sport".as_ref(), &sport_name[..]], bump = sport.bump)]
+    pub sport: Box<Account<'info, Sport>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + std::mem::size_of::<Player>(),
+        seeds = [b"player".as_ref(), &sport_name[..], &external_id.to_le_bytes()],
+        bump,
+    )]
+    pub player: Account<'info, Player>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePlayer<'info> {
+    #[account(mut)]
+    pub player: Account<'info, Player>,
+    pub authority: Signer<'info>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+}
+
+#[derive(Accounts)]
+pub struct LockBetPool<'info> {
+    #[account(mut)]
+    pub bet_pool: Account<'info, BetPool>,
+    pub authority: Signer<'info>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeResult<'info> {
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+
+    #[account(mut)]
+    pub bet_pool: Account<'info, BetPool>,
+
+    #[account(seeds = [b"sport".as_ref(), &bet_pool.sport_name[..]], bump = sport.bump)]
+    pub sport: Box<Account<'info, Sport>>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = disputer,
+        space = 8 + std::mem::size_of::<Dispute>(),
+        seeds = [b"dispute", bet_pool.key().as_ref()],
+        bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    pub arbitrator: Signer<'info>,
+
+    #[account(mut)]
+    pub bet_pool: Account<'info, BetPool>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = disputer,
+        has_one = disputer,
+        seeds = [b"dispute", bet_pool.key().as_ref()],
+        bump = dispute.bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(mut)]
+    pub disputer: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"insurance_fund"], bump)]
+    pub insurance_fund: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireDispute<'info> {
+    #[account(mut)]
+    pub bet_pool: Account<'info, BetPool>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = disputer,
+        has_one = disputer,
+        seeds = [b"dispute", bet_pool.key().as_ref()],
+        bump = dispute.bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(mut)]
+    pub disputer: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeResult<'info> {
+    #[account(mut, has_one = fixture)]
+    pub bet_pool: Account<'info, BetPool>,
+    pub fixture: Account<'info, Fixture>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureBetPool<'info> {
+    #[account(mut)]
+    pub bet_pool: Account<'info, BetPool>,
+    #[account(seeds = [b"sport".as_ref(), &bet_pool.sport_name[..]], bump = sport.bump)]
+    pub sport: Box<Account<'info, Sport>>,
+    pub authority: Signer<'info>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeBetPoolsBatch<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+
+    pub fixture: Account<'info, Fixture>,
+
+    #[account(seeds = [b"sport".as_ref(), &fixture.sport_name[..]], bump = sport.bump)]
+    pub sport: Box<Account<'info, Sport>>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    // One fee vault shared by every pool on the fixture
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + std::mem::size_of::<FeeVault>(),
+        seeds = [b"fee_vault", fixture.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(fixture_id: u64, player_id: Pubkey, stat_name: [u8; 32], stat_line: u32, sport_name: [u8; 32])]
+pub struct InitializeBetPool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+
+    #[account(
+        seeds = [b"fixture".as_ref(), &fixture_id.to_le_bytes()],
+        bump = fixture.bump,
+    )]
+    pub fixture: Account<'info, Fixture>,
+
+    #[account(address = player_id @ ErrorCode::PlayerMismatch)]
+    pub player: Account<'info, Player>,
+
+    #[account(seeds = [b"sport".as_ref(), &sport_name[..]], bump = sport.bump)]
+    pub sport: Box<Account<'info, Sport>>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + std::mem::size_of::<BetPool>(),
+        seeds = [
+            b"bet_pool",
+            &fixture_id.to_le_bytes(),
+            &sport_name[..],
+            player_id.as_ref(),
+            &stat_name,
+            &stat_line.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub bet_pool: Account<'info, BetPool>,
+
+#[account(
+    init,
+    payer = admin,
+    space = 8 + std::mem::size_of::<FeeVault>(),
+    seeds = [b"fee_vault", bet_pool.key().as_ref()],
+    bump
+)]
+pub fee_vault: Account<'info, FeeVault>,
+
+#[account(
+    init,
+    payer = admin,
+    space = 8 + std::mem::size_of::<BetVault>(),
+    seeds = [b"bet_vault", bet_pool.key().as_ref()],
+    bump
+)]
+pub bet_vault: Account<'info, BetVault>,
+
+    pub system_program: Program<'info, System>,
+}
+
+
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct CreateSession<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + std::mem::size_of::<SessionToken>(),
+        seeds = [b"session", owner.key().as_ref(), session_key.as_ref()],
+        bump
+    )]
+    pub session_token: Account<'info, SessionToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut, close = owner, has_one = owner)]
+    pub session_token: Account<'info, SessionToken>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(seeds = [b"blocked", owner.key().as_ref()], bump)]
+    pub owner_block: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + std::mem::size_of::<Balance>(),
+        seeds = [b"balance", owner.key().as_ref()],
+        bump
+    )]
+    pub balance: Account<'info, Balance>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(seeds = [b"blocked", owner.key().as_ref()], bump)]
+    pub owner_block: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [b"balance", owner.key().as_ref()],
+        bump = balance.bump
+    )]
+    pub balance: Account<'info, Balance>,
+}
+
+#[derive(Accounts)]
+pub struct SetLimits<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = 8 + std::mem::size_of::<UserProfile>(),
+        seeds = [b"user_profile", wallet.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct InitializeUserProfile<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<UserProfile>(),
+        seeds = [b"user_profile", wallet.as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(fixture_id: u64, player_id: Pubkey, stat_name: [u8; 32], stat_line: u32, sport_name: [u8; 32])]
+pub struct PlaceBet<'info> {
+    // Owner of the pick; signs directly or delegates to a session key
+    #[account(mut)]
+    pub bettor: SystemAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    /// Funds rent for every account this instruction creates. Required: Anchor's `init`
+    /// needs a concrete payer, so pass the bettor here when nobody sponsors the bet.
+    /// Only the `UserPick` rent is refunded (via `close_user_pick`); the nonce and profile
+    /// are per-wallet and outlive the pick, and SPL mints and the holder's ATA cannot be
+    /// reclaimed by the program, so a sponsor pays those for good.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    #[account(mut, has_one = fee_vault, has_one = fixture)]
+    pub bet_pool: Account<'info, BetPool>,
+
+    pub fixture: Account<'info, Fixture>,
+
+    #[account(seeds = [b"sport".as_ref(), &bet_pool.sport_name[..]], bump = sport.bump)]
+    pub sport: Box<Account<'info, Sport>>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(address = bet_pool.player_id @ ErrorCode::PlayerMismatch)]
+    pub player: Account<'info, Player>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + 8,
+        seeds = [b"user_nonce", bettor.key().as_ref(), bet_pool.key().as_ref()],
+        bump
+    )]
+    pub user_nonce: Account<'info, UserNonce>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<UserProfile>(),
+        seeds = [b"user_profile", bettor.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    pub attestation: Option<Account<'info, Attestation>>,
+
+    #[account(seeds = [b"blocked", bettor.key().as_ref()], bump)]
+    pub bettor_block: UncheckedAccount<'info>,
+
+    // Stake is drawn from the internal balance when supplied
+    #[account(mut)]
+    pub bettor_balance: Option<Account<'info, Balance>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<UserPick>(),
+        seeds = [
+            b"user_pick",
+            bettor.key().as_ref(),
+            bet_pool.key().as_ref(),
+            &user_nonce.count.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub user_pick: Account<'info, UserPick>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"mint", user_pick.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = mint_authority,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"mint", user_pick.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user_token_account: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(
+        mut,
+        seeds = [b"bet_vault", bet_pool.key().as_ref()],
+        bump = bet_vault.bump
+    )]
+    pub bet_vault: Account<'info, BetVault>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[account]
+pub struct UserPick {
+    pub owner: Pubkey,
+    pub bet_amount: u64,
+    pub pick_side: bool,
+    pub pool: Pubkey,
+    pub claimed: bool,
+    pub mint: Pubkey,
+    pub bump: u8,
+    pub sport_name: [u8; 32],
+    pub for_sale: bool,
+    pub rent_payer: Pubkey,
+}
+
+// Delegated signing key with a spend cap, expiry and SESSION_* instruction mask
+#[account]
+pub struct SessionToken {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub spend_cap: u64,
+    pub spent: u64,
+    pub expires_at: i64,
+    pub allowed_instructions: u8,
+    pub bump: u8,
+}
+
+// Internal ledger; the lamports for `amount` sit in this PDA above its rent floor
+#[account]
+pub struct Balance {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(Default)]
+pub struct UserProfile {
+    pub wallet: Pubkey,
+    pub total_wagered: u64,
+    pub total_won: u64,
+    pub total_fees_paid: u64,
+    pub total_purchased: u64,
+    pub total_sold: u64,
+    pub pick_count: u64,
+    pub win_count: u32,
+    pub loss_count: u32,
+    pub push_count: u32,
+    // Positive for consecutive wins, negative for consecutive losses
+    pub current_streak: i32,
+
+    // Responsible-gaming controls
+    pub limits: ResponsibleLimits,
+    pub pending_limits: ResponsibleLimits,
+    pub pending_limits_at: i64,
+    pub self_excluded_until: i64,
+    // Spend tracked per UTC day / week since the epoch
+    pub day_index: i64,
+    pub day_deposited: u64,
+    pub week_index: i64,
+    pub week_deposited: u64,
+    pub week_returned: u64,
+
+    pub bump: u8,
+}
+
+impl UserProfile {
+    // Profiles are created lazily, so this is a no-op once the wallet is set
+    pub fn init(&mut self, wallet: Pubkey, bump: u8) {
+        if self.wallet == Pubkey::default() {
+            self.wallet = wallet;
+            self.bump = bump;
+        }
+    }
+
+    pub fn record_bet(&mut self, amount: u64, fee: u64) {
+        self.total_wagered += amount;
+        self.total_fees_paid += fee;
+        self.pick_count += 1;
+    }
+
+    pub fn apply_due_limits(&mut self, now: i64) {
+        if self.pending_limits_at != 0 && now >= self.pending_limits_at {
+            self.limits = self.pending_limits;
+            self.pending_limits = ResponsibleLimits::default();
+            self.pending_limits_at = 0;
+        }
+    }
+
+    fn roll_windows(&mut self, now: i64) {
+        if now / SECONDS_PER_DAY != self.day_index {
+            self.day_index = now / SECONDS_PER_DAY;
+            self.day_deposited = 0;
+        }
+        if now / SECONDS_PER_WEEK != self.week_index {
+            self.week_index = now / SECONDS_PER_WEEK;
+            self.week_deposited = 0;
+            self.week_returned = 0;
+        }
+    }
+
+    // Money put at risk through bets or marketplace purchases
+    pub fn record_deposit(&mut self, amount: u64, now: i64) -> Result<()> {
+        require!(now >= self.self_excluded_until, ErrorCode::SelfExcluded);
+        self.apply_due_limits(now);
+        self.roll_windows(now);
+
+        let limits = self.limits;
+        require!(
+            limits.daily_deposit_limit == 0
+                || self.day_deposited + amount <= limits.daily_deposit_limit,
+            ErrorCode::DailyDepositLimitReached
+        );
+        require!(
+            limits.weekly_deposit_limit == 0
+                || self.week_deposited + amount <= limits.weekly_deposit_limit,
+            ErrorCode::WeeklyDepositLimitReached
+        );
+        let week_loss = self.week_deposited.saturating_sub(self.week_returned);
+        require!(
+            limits.weekly_loss_limit == 0 || week_loss + amount <= limits.weekly_loss_limit,
+            ErrorCode::LossLimitReached
+        );
+
+        self.day_deposited += amount;
+        self.week_deposited += amount;
+        Ok(())
+    }
+
+    // Resale proceeds offset the seller's weekly loss like any other return
+    pub fn record_sale(&mut self, proceeds: u64, royalty: u64, now: i64) {
+        self.roll_windows(now);
+        self.week_returned += proceeds;
+        self.total_sold += proceeds;
+        self.total_fees_paid += royalty;
+    }
+
+    // Every payout path (claim, crank, emergency refund) must report here
+    pub fn record_settlement(&mut self, settlement: Settlement, now: i64) {
+        self.roll_windows(now);
+        self.week_returned += settlement.payout();
+
+        match settlement {
+            Settlement::Won(amount) => {
+                self.total_won += amount;
+                self.win_count += 1;
+                self.current_streak = self.current_streak.max(0) + 1;
+            }
+            Settlement::Lost => {
+                self.loss_count += 1;
+                self.current_streak = self.current_streak.min(0) - 1;
+            }
+            Settlement::Push => self.push_count += 1,
+            // Voided fixtures don't count towards the record
+            Settlement::Refund(_) => {}
+        }
+    }
+}
+
+// Zero means no limit
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResponsibleLimits {
+    pub daily_deposit_limit: u64,
+    pub weekly_deposit_limit: u64,
+    pub weekly_loss_limit: u64,
+}
+
+impl ResponsibleLimits {
+    // Field-wise strictest of the two
+    pub fn tightest(&self, other: &ResponsibleLimits) -> ResponsibleLimits {
+        let strictest = |a: u64, b: u64| match (a, b) {
+            (0, b) => b,
+            (a, 0) => a,
+            (a, b) => a.min(b),
+        };
+        ResponsibleLimits {
+            daily_deposit_limit: strictest(self.daily_deposit_limit, other.daily_deposit_limit),
+            weekly_deposit_limit: strictest(self.weekly_deposit_limit, other.weekly_deposit_limit),
+            weekly_loss_limit: strictest(self.weekly_loss_limit, other.weekly_loss_limit),
+        }
+    }
+}
+
+#[account]
+pub struct UserNonce {
+    pub count: u64,
+}
+
+#[derive(Accounts)]
+pub struct CloseUserPick<'info> {
+    #[account(mut, close = rent_payer)]
+    pub user_pick: Account<'info, UserPick>,
+
+    #[account(mut, address = user_pick.rent_payer @ ErrorCode::Unauthorized)]
+    pub rent_payer: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DelistPick<'info> {
+    #[account(mut, address = user_pick.owner @ ErrorCode::Unauthorized)]
+    pub seller: Signer<'info>,
+
+    #[account(mut, has_one = mint)]
+    pub user_pick: Account<'info, UserPick>,
+
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut, token::mint = mint, token::authority = seller)]
+    pub seller_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_pda,
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"escrow", user_pick.key().as_ref()],
+        bump
+    )]
+    pub escrow_pda: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+
+
+#[derive(Accounts)]
+pub struct BuyPickNFT<'info> {
+    #[account(mut, address = user_pick.owner @ ErrorCode::Unauthorized)]
+    pub seller: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(mut, has_one = mint, has_one = pool)]
+    pub user_pick: Account<'info, UserPick>,
+
+    #[account(mut)]
+    pub mint: Account<'info, token::Mint>,
+
+    #[account(mut)]
+    pub pool: Account<'info, BetPool>,
+
+    #[account(mut)]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    #[account()]
+    pub escrow_pda: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + std::mem::size_of::<UserProfile>(),
+        seeds = [b"user_profile", buyer.key().as_ref()],
+        bump
+    )]
+    pub buyer_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + std::mem::size_of::<UserProfile>(),
+        seeds = [b"user_profile", seller.key().as_ref()],
+        bump
+    )]
+    pub seller_profile: Account<'info, UserProfile>,
+
+    pub attestation: Option<Account<'info, Attestation>>,
+
+    #[account(seeds = [b"blocked", buyer.key().as_ref()], bump)]
+    pub buyer_block: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"blocked", seller.key().as_ref()], bump)]
+    pub seller_block: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub buyer_balance: Option<Account<'info, Balance>>,
+
+    #[account(mut)]
+    pub seller_balance: Option<Account<'info, Balance>>,
+
+    #[account(mut, address = pool.fee_vault @ ErrorCode::InvalidFeeVault)]
+    pub royalty_vault: Account<'info, FeeVault>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [b"sport".as_ref(), &pool.sport_name[..]], bump = sport.bump)]
+    pub sport: Box<Account<'info, Sport>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+
+#[derive(Accounts)]
+pub struct ListPickNFT<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(mut, has_one = pool, has_one = mint)]
+    pub user_pick: Account<'info, UserPick>,
+
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub pool: Account<'info, BetPool>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [b"sport".as_ref(), &pool.sport_name[..]], bump = sport.bump)]
+    pub sport: Box<Account<'info, Sport>>,
+
+    #[account(seeds = [b"blocked", seller.key().as_ref()], bump)]
+    pub seller_block: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_pda
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"escrow", user_pick.key().as_ref()],
+        bump
+    )]
+    pub escrow_pda: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimUnsoldPick<'info> {
+    #[account(
+        seeds = [b"escrow", user_pick.key().as_ref()],
+        bump
+    )]
+    pub escrow_pda: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_pda,
+        constraint = escrow_token_account.amount == 1 @ ErrorCode::InvalidTokenBalance,
+        constraint = escrow_token_account.delegate == COption::None @ ErrorCode::UnexpectedDelegate,
+        constraint = escrow_token_account.close_authority == COption::None @ ErrorCode::UnexpectedCloseAuthority,
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint, token::authority = seller)]
+    pub seller_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut, has_one = mint, has_one = pool)]
+    pub user_pick: Account<'info, UserPick>,
+
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub pool: Account<'info, BetPool>,
+
+    // Unsold picks only ever go back to whoever listed them
+    #[account(mut, address = user_pick.owner @ ErrorCode::Unauthorized)]
+    pub seller: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+
+
+#[account]
+pub struct Fixture {
+    pub fixture_id: u64,
+    pub sport_name: [u8; 32],
+    pub home_team: [u8; 32],
+    pub away_team: [u8; 32],
+    pub scheduled_start: i64,
+    pub status: FixtureStatus,
+    pub bump: u8,
+}
+
+#[account]
+pub struct Config {
+    pub treasury: Pubkey,
+    pub claim_period: i64,
+    pub unclaimed_destination: UnclaimedDestination,
+    pub resolution_timeout: i64,
+    pub dispute_window: i64,
+    pub dispute_timeout: i64,
+    pub arbitrator: Pubkey,
+    pub dispute_bond: u64,
+    pub dispute_reward: u64,
+    pub correction_window: i64,
+    pub crank_tip: u64,
+    // Marketplace resale royalty, kept out of ConfigParams and set via CouncilAction::SetRoyalty
+    pub royalty_bps: u16,
+    pub guardian: Pubkey,
+    pub paused_flags: u8,
+    pub bump: u8,
+}
+
+impl Config {
+    pub fn apply(&mut self, params: &ConfigParams) {
+        self.treasury = params.treasury;
+        self.claim_period = params.claim_period;
+        self.unclaimed_destination = params.unclaimed_destination;
+        self.resolution_timeout = params.resolution_timeout;
+        self.dispute_window = params.dispute_window;
+        self.dispute_timeout = params.dispute_timeout;
+        self.arbitrator = params.arbitrator;
+        self.dispute_bond = params.dispute_bond;
+        self.dispute_reward = params.dispute_reward;
+        self.correction_window = params.correction_window;
+        self.crank_tip = params.crank_tip;
+        self.guardian = params.guardian;
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigParams {
+    pub treasury: Pubkey,
+    // Seconds after publication during which winners may claim
+    pub claim_period: i64,
+    pub unclaimed_destination: UnclaimedDestination,
+    // Seconds after the betting deadline before unresolved pools can be refunded
+    pub resolution_timeout: i64,
+    // Seconds a proposed result stays open to disputes before it can be finalized
+    pub dispute_window: i64,
+    // Seconds the arbitrator has to rule before an open dispute lapses
+    pub dispute_timeout: i64,
+    pub arbitrator: Pubkey,
+    pub dispute_bond: u64,
+    // Paid from the insurance fund to disputers who were right
+    pub dispute_reward: u64,
+    // Seconds after first publication during which official stat corrections are accepted
+    pub correction_window: i64,
+    // Lamports paid from the fee vault per pick settled by crank_settle
+    pub crank_tip: u64,
+    // Emergency key that can pause anything but never unpause
+    pub guardian: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseScope {
+    Global,
+    Sport,
+    Pool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnclaimedDestination {
+    Treasury,
+    Jackpot,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Role {
+    PoolCreator,
+    Resolver,
+    Treasurer,
+    Pauser,
+    // No longer checked anywhere; kept so the role seeds after it don't shift
+    MarketplaceAdmin,
+    Compliance,
+}
+
+// One PDA per (role, grantee); closing it revokes the role
+#[account]
+pub struct RoleGrant {
+    pub grantee: Pubkey,
+    pub role: Role,
+    pub granted_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct Council {
+    pub members: [Pubkey; MAX_COUNCIL_MEMBERS],
+    pub member_count: u8,
+    pub threshold: u8,
+    pub proposal_count: u64,
+    // Incremented on every rotation; proposals from earlier epochs are dead
+    pub epoch: u64,
+    pub bump: u8,
+}
+
+impl Council {
+    pub fn set_members(&mut self, members: &[Pubkey], threshold: u8) {
+        self.members = [Pubkey::default(); MAX_COUNCIL_MEMBERS];
+        self.members[..members.len()].copy_from_slice(members);
+        self.member_count = members.len() as u8;
+        self.threshold = threshold;
+    }
+
+    pub fn member_index(&self, key: &Pubkey) -> Option<usize> {
+        self.members[..self.member_count as usize]
+            .iter()
+            .position(|m| m == key)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CouncilAction {
+    PublishResult { bet_pool: Pubkey, final_stat: u32 },
+    // Only the first stat_component_count values are read
+    PublishCompositeResult { bet_pool: Pubkey, component_values: [u32; MAX_STAT_COMPONENTS] },
+    WithdrawFees { bet_pool: Pubkey, amount: u64 },
+    // Pays out forfeited winnings collected under UnclaimedDestination::Jackpot
+    WithdrawJackpot { recipient: Pubkey, amount: u64 },
+    UpdateConfig(ConfigParams),
+    SetRoyalty { royalty_bps: u16 },
+    UpdateCouncil { members: [Pubkey; MAX_COUNCIL_MEMBERS], member_count: u8, threshold: u8 },
+}
+
+#[account]
+pub struct Proposal {
+    pub council: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub epoch: u64,
+    pub action: CouncilAction,
+    // Bit i is set once members[i] has approved
+    pub approvals: u16,
+    pub approval_count: u8,
+    pub executed: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+// Sanctions block; doubles as the escrow for winnings frozen while blocked
+#[account]
+pub struct BlockedWallet {
+    pub wallet: Pubkey,
+    pub blocked_by: Pubkey,
+    pub reason_code: u16,
+    pub blocked_at: i64,
+    pub held_amount: u64,
+    pub bump: u8,
+}
+
+// KYC/geo attestation, one per wallet
+#[account]
+pub struct Attestation {
+    pub wallet: Pubkey,
+    pub issuer: Pubkey,
+    // ISO 3166-1 alpha-2, e.g. b"US"
+    pub jurisdiction: [u8; 2],
+    pub issued_at: i64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+pub trait LamportVault {
+    fn owner_key(&self) -> Pubkey;
+    fn record_deposit(&mut self, amount: u64);
+    fn record_withdrawal(&mut self, amount: u64);
+}
+
+// Fee vaults are owned by a pool, or by a fixture when shared across a batch
+#[account]
+#[derive(Default)]
+pub struct FeeVault {
+    pub owner: Pubkey,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub bump: u8,
+}
+
+impl FeeVault {
+    pub fn balance(&self) -> u64 {
+        self.total_deposited.saturating_sub(self.total_withdrawn)
+    }
+}
+
+impl LamportVault for FeeVault {
+    fn owner_key(&self) -> Pubkey {
+        self.owner
+    }
+
+    fn record_deposit(&mut self, amount: u64) {
+        self.total_deposited += amount;
+    }
+
+    fn record_withdrawal(&mut self, amount: u64) {
+        self.total_withdrawn += amount;
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct BetVault {
+    pub owner: Pubkey,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub bump: u8,
+}
+
+impl BetVault {
+    pub fn balance(&self) -> u64 {
+        self.total_deposited.saturating_sub(self.total_withdrawn)
+    }
+}
+
+impl LamportVault for BetVault {
+    fn owner_key(&self) -> Pubkey {
+        self.owner
+    }
+
+    fn record_deposit(&mut self, amount: u64) {
+        self.total_deposited += amount;
+    }
+
+    fn record_withdrawal(&mut self, amount: u64) {
+        self.total_withdrawn += amount;
+    }
+}
+
+#[account]
+pub struct Dispute {
+    pub pool: Pubkey,
+    pub disputer: Pubkey,
+    pub proposed_stat: u32,
+    pub bond: u64,
+    pub opened_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct Sport {
+    pub sport_name: [u8; 32],
+    pub stat_types: [StatType; MAX_STAT_TYPES],
+    pub stat_type_count: u8,
+    pub paused_flags: u8,
+    pub bump: u8,
+}
+
+impl Sport {
+    pub fn stat_type(&self, stat_name: &[u8; 32]) -> Option<&StatType> {
+        self.stat_types[..self.stat_type_count as usize]
+            .iter()
+            .find(|t| t.stat_name == *stat_name)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatType {
+    pub stat_name: [u8; 32],
+    pub unit: [u8; 16],
+    // Values are stored as integers scaled by 10^decimals
+    pub decimals: u8,
+    pub min_value: u32,
+    pub max_value: u32,
+}
+
+impl StatType {
+    pub fn is_plausible(&self, value: u32) -> bool {
+        value >= self.min_value && value <= self.max_value
+    }
+}
+
+#[account]
+pub struct Player {
+    pub sport_name: [u8; 32],
+    pub external_id: u64,
+    pub display_name: [u8; 32],
+    pub team: [u8; 32],
+    pub active: bool,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixtureStatus {
+    Scheduled,
+    Live,
+    Final,
+    Postponed,
+}
+
+impl FixtureStatus {
+    pub fn can_transition_to(self, next: FixtureStatus) -> bool {
+        matches!(
+            (self, next),
+            (FixtureStatus::Scheduled, FixtureStatus::Live)
+                | (FixtureStatus::Scheduled, FixtureStatus::Postponed)
+                | (FixtureStatus::Live, FixtureStatus::Final)
+                | (FixtureStatus::Live, FixtureStatus::Postponed)
+        )
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct BetPool {
+    pub fixture_id: u64,
+    pub fixture: Pubkey,
+    pub sport_name: [u8; 32],
+    pub player_id: Pubkey,
+    pub stat_name: [u8; 32],
+    pub stat_line: u32,
+    pub deadline: i64,
+    pub resolution_timeout: i64,
+    pub correction_window: i64,
+
+    pub total_over_amount: u64,
+    pub total_under_amount: u64,
+
+    pub fee_vault: Pubkey,
+    pub status: PoolStatus,
+    // First time a result was proposed
+    pub published_at: i64,
+    pub dispute_window_end: i64,
+    pub dispute_open: bool,
+
+    // Audit trail of post-publication stat corrections
+    pub amendments: [StatAmendment; MAX_STAT_AMENDMENTS],
+    pub amendment_count: u8,
+    pub claim_deadline: i64,
+    pub final_stat: u32,
+
+    pub pick_count: u64,
+    pub settled_count: u64,
+    pub unclaimed_swept: u64,
+
+    // Exact parimutuel accounting
+    pub total_paid_out: u64,
+    pub winning_stake_claimed: u64,
+
+    pub total_fees_collected: u64,
+    pub total_fees_disbursed: u64,
+
+    // Composite props (e.g. PRA); empty for single-stat pools
+    pub stat_components: [StatComponent; MAX_STAT_COMPONENTS],
+    pub stat_component_count: u8,
+    pub component_values: [u32; MAX_STAT_COMPONENTS],
+
+    // Compliance gate; an empty jurisdiction list accepts any attested wallet
+    pub requires_attestation: bool,
+    pub allowed_jurisdictions: [[u8; 2]; MAX_JURISDICTIONS],
+    pub jurisdiction_count: u8,
+
+    pub paused_flags: u8,
+    pub bump: u8,
+}
+
+impl BetPool {
+    pub fn new(
+        fixture: &Account<Fixture>,
+        spec: &PoolSpec,
+        fee_vault: Pubkey,
+        config: &Config,
+        bump: u8,
+    ) -> Self {
+        BetPool {
+            fixture_id: fixture.fixture_id,
+            fixture: fixture.key(),
+            sport_name: fixture.sport_name,
+            player_id: spec.player_id,
+            stat_name: spec.stat_name,
+            stat_line: spec.stat_line,
+            deadline: fixture.scheduled_start,
+            fee_vault,
+            resolution_timeout: config.resolution_timeout,
+            correction_window: config.correction_window,
+            bump,
+            ..Default::default()
+        }
+    }
+
+    // Betting closes at the deadline even if nobody has locked the pool explicitly
+    pub fn is_locked(&self, now: i64) -> bool {
+        self.status == PoolStatus::Locked
+            || (self.status == PoolStatus::Open && now >= self.deadline)
+    }
+
+    pub fn resolution_timed_out(&self, now: i64) -> bool {
+        !self.status.has_result() && now > self.deadline + self.resolution_timeout
+    }
+
+    pub fn correction_window_end(&self) -> i64 {
+        self.published_at + self.correction_window
+    }
+
+    // The first call publishes; later calls are amendments inside the correction window
+    pub fn propose_result(
+        &mut self,
+        final_stat: u32,
+        authority: Pubkey,
+        now: i64,
+        dispute_window: i64,
+    ) -> Result<()> {
+        require!(self.status != PoolStatus::Finalized, ErrorCode::AlreadyPublished);
+        require!(!self.resolution_timed_out(now), ErrorCode::ResolutionTimedOut);
+        require!(!self.dispute_open, ErrorCode::DisputeOpen);
+
+        if self.status == PoolStatus::Proposed {
+            require!(now < self.correction_window_end(), ErrorCode::CorrectionWindowClosed);
+            require!(
+                (self.amendment_count as usize) < MAX_PROPOSER_AMENDMENTS,
+                ErrorCode::TooManyAmendments
+            );
+            self.record_amendment(final_stat, authority, now);
+        } else {
+            require!(self.is_locked(now), ErrorCode::PoolNotLocked);
+            self.published_at = now;
+            self.status = PoolStatus::Proposed;
+            self.final_stat = final_stat;
+        }
+        self.dispute_window_end = now + dispute_window;
+        Ok(())
+    }
+
+    pub fn record_amendment(&mut self, new_stat: u32, authority: Pubkey, now: i64) {
+        self.amendments[self.amendment_count as usize] = StatAmendment {
+            previous_stat: self.final_stat,
+            new_stat,
+            amended_at: now,
+            authority,
+        };
+        self.amendment_count += 1;
+        self.final_stat = new_stat;
+    }
+
+    // Voided or timed-out pools never publish, so their claim window runs from the timeout
+    pub fn effective_claim_deadline(&self, claim_period: i64) -> i64 {
+        if self.status == PoolStatus::Finalized {
+            self.claim_deadline
+        } else {
+            self.deadline + self.resolution_timeout + claim_period
+        }
+    }
+
+    pub fn settlement_for(&self, user_pick: &UserPick, voided: bool) -> Settlement {
+        // Handle one-sided refund (no opposite picks)
+        let has_only_over = self.total_under_amount == 0;
+        let has_only_under = self.total_over_amount == 0;
+        if voided || has_only_over || has_only_under {
+            return Settlement::Refund(user_pick.bet_amount);
+        }
+
+        let winner_is_over = match self.final_stat.cmp(&self.stat_line) {
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Less => false,
+            std::cmp::Ordering::Equal => return Settlement::Push, // Push/tie, no payout
+        };
+        if user_pick.pick_side != winner_is_over {
+            return Settlement::Lost;
+        }
+
+        let user_share = (user_pick.bet_amount as u128)
+            .checked_mul(self.total_pool() as u128)
+            .unwrap()
+            / (self.winning_pool() as u128);
+        Settlement::Won(user_share as u64)
+    }
+
+    pub fn total_pool(&self) -> u64 {
+        self.total_over_amount + self.total_under_amount
+    }
+
+    pub fn winning_pool(&self) -> u64 {
+        if self.final_stat > self.stat_line {
+            self.total_over_amount
+        } else {
+            self.total_under_amount
+        }
+    }
+
+    pub fn allows_jurisdiction(&self, jurisdiction: &[u8; 2]) -> bool {
+        self.jurisdiction_count == 0
+            || self.allowed_jurisdictions[..self.jurisdiction_count as usize].contains(jurisdiction)
+    }
+
+    // Invariant: the pool can never pay out more than was staked into it
+    pub fn record_payout(&mut self, amount: u64) -> Result<()> {
+        let total_paid_out = self
+            .total_paid_out
+            .checked_add(amount)
+            .ok_or(ErrorCode::VaultOverdrawn)?;
+        require!(total_paid_out <= self.total_pool(), ErrorCode::VaultOverdrawn);
+        self.total_paid_out = total_paid_out;
+        Ok(())
+    }
+
+    pub fn composite_stat(&self, component_values: &[u32]) -> Result<u32> {
+        let mut total: u64 = 0;
+        for (component, value) in self.stat_components.iter().zip(component_values) {
+            let weighted = (*value as u64)
+                .checked_mul(component.weight as u64)
+                .ok_or(ErrorCode::StatOverflow)?;
+            total = total.checked_add(weighted).ok_or(ErrorCode::StatOverflow)?;
+        }
+        u32::try_from(total / STAT_WEIGHT_SCALE).map_err(|_| error!(ErrorCode::StatOverflow))
+    }
+}
+
+#[event]
+pub struct ResultFinalized {
+    pub pool: Pubkey,
+    pub final_stat: u32,
+    pub claim_deadline: i64,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub pool: Pubkey,
+    pub disputer: Pubkey,
+    pub upheld: bool,
+    pub final_stat: u32,
+    pub reward: u64,
+}
+
+#[event]
+pub struct PauseChanged {
+    pub scope: PauseScope,
+    pub target: Pubkey,
+    pub previous_flags: u8,
+    pub flags: u8,
+    pub authority: Pubkey,
+    pub reason_code: u16,
+}
+
+#[event]
+pub struct PicksCranked {
+    pub pool: Pubkey,
+    pub settled: u64,
+    pub paid_out: u64,
+    pub tip: u64,
+}
 
-#[account(
-    init,
-    payer = admin,
-    space = 8 + 0,
-    seeds = [b"bet_vault", bet_pool.key().as_ref()],
-    bump
-)]
-pub bet_vault: UncheckedAccount<'info>,
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuditReport {
+    pub pool: Pubkey,
+    pub bet_vault_expected: u64,
+    pub bet_vault_actual: u64,
+    // Positive means surplus, negative means a shortfall
+    pub bet_vault_discrepancy: i64,
+    pub fee_vault_shared: bool,
+    pub fee_vault_expected: u64,
+    pub fee_vault_actual: u64,
+    pub fee_vault_discrepancy: i64,
+    pub solvent: bool,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct PoolAudited {
+    pub report: AuditReport,
 }
 
+#[event]
+pub struct UnclaimedSwept {
+    pub pool: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub unsettled_picks: u64,
+}
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Settlement {
+    Refund(u64),
+    Won(u64),
+    Lost,
+    Push,
+}
 
-#[derive(Accounts)]
-#[instruction(fixture_id: u64, player_id: Pubkey, stat_name: [u8; 32], stat_line: u32, sport_name: [u8; 32])]
-pub struct PlaceBet<'info> {
-    #[account(mut, signer)]
-    pub bettor: Signer<'info>,
+impl Settlement {
+    pub fn payout(self) -> u64 {
+        match self {
+            Settlement::Refund(amount) | Settlement::Won(amount) => amount,
+            Settlement::Lost | Settlement::Push => 0,
+        }
+    }
+}
 
-    #[account(mut, has_one = fee_vault)]
-    pub bet_pool: Account<'info, BetPool>,
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PoolStatus {
+    #[default]
+    Open,
+    Locked,
+    Proposed,
+    Finalized,
+}
 
-    #[account(
-        init_if_needed,
-        payer = bettor,
-        space = 8 + 8,
-        seeds = [b"user_nonce", bettor.key().as_ref(), bet_pool.key().as_ref()],
-        bump
-    )]
-    pub user_nonce: Account<'info, UserNonce>,
+impl PoolStatus {
+    pub fn has_result(self) -> bool {
+        matches!(self, PoolStatus::Proposed | PoolStatus::Finalized)
+    }
+}
 
-    #[account(
-        init,
-        payer = bettor,
-        space = 8 + std::mem::size_of::<UserPick>(),
-        seeds = [
-            b"user_pick",
-            bettor.key().as_ref(),
-            bet_pool.key().as_ref(),
-            &user_nonce.count.to_le_bytes(),
-        ],
-        bump
-    )]
-    pub user_pick: Account<'info, UserPick>,
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolSpec {
+    pub player_id: Pubkey,
+    pub stat_name: [u8; 32],
+    pub stat_line: u32,
+}
 
-    #[account(
-        init_if_needed,
-        payer = bettor,
-        seeds = [b"mint", user_pick.key().as_ref()],
-        bump,
-        mint::decimals = 0,
-        mint::authority = mint_authority,
-    )]
-    pub mint: Account<'info, Mint>,
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatAmendment {
+    pub previous_stat: u32,
+    pub new_stat: u32,
+    pub amended_at: i64,
+    pub authority: Pubkey,
+}
 
-    #[account(
-        seeds = [b"mint", user_pick.key().as_ref()],
-        bump
-    )]
-    pub mint_authority: UncheckedAccount<'info>,
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatComponent {
+    pub stat_name: [u8; 32],
+    pub weight: u32,
+}
 
-    #[account(mut)]
-    pub user_token_account: AccountInfo<'info>,
 
-    #[account(mut)]
-    pub fee_vault: UncheckedAccount<'info>,
 
+#[derive(Accounts)]
+pub struct SettleClaim<'info> {
     #[account(mut)]
-    pub bet_vault: UncheckedAccount<'info>,
+    pub user_pick: Account<'info, UserPick>,
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+    #[account(mut, has_one = fixture)]
+    pub bet_pool: Account<'info, BetPool>,
 
-#[account]
-pub struct UserPick {
-    pub owner: Pubkey,
-    pub bet_amount: u64,
-    pub pick_side: bool,
-    pub pool: Pubkey,
-    pub claimed: bool,
-    pub mint: Pubkey,
-    pub bump: u8,
-    pub sport_name: [u8; 32],
-    pub for_sale: bool,
-}
+    pub fixture: Account<'info, Fixture>,
 
-#[account]
-pub struct UserNonce {
-    pub count: u64,
-}
+    #[account(seeds = [b"sport".as_ref(), &bet_pool.sport_name[..]], bump = sport.bump)]
+    pub sport: Box<Account<'info, Sport>>,
 
-#[derive(Accounts)]
-pub struct DelistPick<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    // Payouts go to whoever currently holds the pick NFT
     #[account(mut)]
-    pub seller: Signer<'info>,
+    pub recipient: SystemAccount<'info>,
 
+    // The recipient itself or one of its session keys; funds the profile if it's missing
     #[account(mut)]
-    pub user_pick: Account<'info, UserPick>,
+    pub authority: Signer<'info>,
 
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<UserProfile>(),
+        seeds = [b"user_profile", recipient.key().as_ref()],
+        bump
+    )]
+    pub recipient_profile: Account<'info, UserProfile>,
+
+    #[account(mut, seeds = [b"blocked", recipient.key().as_ref()], bump)]
+    pub recipient_block: UncheckedAccount<'info>,
 
     #[account(mut)]
-    pub seller_token_account: Account<'info, TokenAccount>,
+    pub recipient_balance: Option<Account<'info, Balance>>,
 
     #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = escrow_pda,
+        token::mint = user_pick.mint,
+        token::authority = recipient,
+        constraint = recipient_token_account.amount == 1 @ ErrorCode::NotPickHolder,
     )]
-    pub escrow_token_account: Account<'info, TokenAccount>,
+    pub recipient_token_account: Account<'info, TokenAccount>,
 
     #[account(
-        seeds = [b"escrow", user_pick.key().as_ref()],
-        bump
+        mut,
+        seeds = [b"bet_vault", bet_pool.key().as_ref()],
+        bump = bet_vault.bump
     )]
-    pub escrow_pda: UncheckedAccount<'info>,
+    pub bet_vault: Account<'info, BetVault>,
 
-    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
-
-
 #[derive(Accounts)]
-pub struct BuyPickNFT<'info> {
+pub struct CrankSettle<'info> {
     #[account(mut)]
-    pub seller: SystemAccount<'info>,
+    pub cranker: Signer<'info>,
 
-    #[account(mut)]
-    pub buyer: Signer<'info>,
+    #[account(mut, has_one = fixture, has_one = fee_vault)]
+    pub bet_pool: Account<'info, BetPool>,
 
-    #[account(mut, has_one = mint, has_one = pool)]
-    pub user_pick: Account<'info, UserPick>,
+    pub fixture: Account<'info, Fixture>,
 
-    #[account(mut)]
-    pub mint: Account<'info, token::Mint>,
+    #[account(seeds = [b"sport".as_ref(), &bet_pool.sport_name[..]], bump = sport.bump)]
+    pub sport: Box<Account<'info, Sport>>,
 
-    #[account(mut)]
-    pub pool: Account<'info, BetPool>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
 
     #[account(mut)]
-    pub escrow_token_account: Account<'info, TokenAccount>,
-
-    #[account()]
-    pub escrow_pda: UncheckedAccount<'info>,
+    pub fee_vault: Account<'info, FeeVault>,
 
     #[account(
-        init_if_needed,
-        payer = buyer,
-        associated_token::mint = mint,
-        associated_token::authority = buyer
+        mut,
+        seeds = [b"bet_vault", bet_pool.key().as_ref()],
+        bump = bet_vault.bump
     )]
-    pub buyer_token_account: Account<'info, TokenAccount>,
+    pub bet_vault: Account<'info, BetVault>,
 
-    #[account(mut, seeds = [b"fee_vault", pool.key().as_ref()], bump)]
-    pub royalty_vault: UncheckedAccount<'info>,
-
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
-
 #[derive(Accounts)]
-pub struct ListPickNFT<'info> {
+pub struct EmergencyRefund<'info> {
+    // Permissionless; the caller only funds the holder's profile if it's missing
     #[account(mut)]
-    pub seller: Signer<'info>,
+    pub caller: Signer<'info>,
 
-    #[account(mut)]
+    #[account(mut, has_one = pool @ ErrorCode::Unauthorized)]
     pub user_pick: Account<'info, UserPick>,
 
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub pool: Account<'info, BetPool>,
 
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    // Refunds go to whoever currently holds the pick NFT
     #[account(mut)]
-    pub pool: Account<'info, BetPool>,
+    pub holder: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"blocked", holder.key().as_ref()], bump)]
+    pub holder_block: UncheckedAccount<'info>,
 
     #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = seller
+        init_if_needed,
+        payer = caller,
+        space = 8 + std::mem::size_of::<UserProfile>(),
+        seeds = [b"user_profile", holder.key().as_ref()],
+        bump
     )]
-    pub seller_token_account: Account<'info, TokenAccount>,
+    pub holder_profile: Account<'info, UserProfile>,
 
     #[account(
-        init_if_needed,
-        payer = seller,
-        associated_token::mint = mint,
-        associated_token::authority = escrow_pda
+        token::mint = user_pick.mint,
+        token::authority = holder,
+        constraint = holder_token_account.amount == 1 @ ErrorCode::NotPickHolder,
     )]
-    pub escrow_token_account: Account<'info, TokenAccount>,
+    pub holder_token_account: Account<'info, TokenAccount>,
 
     #[account(
-        seeds = [b"escrow", user_pick.key().as_ref()],
-        bump
+        mut,
+        seeds = [b"bet_vault", pool.key().as_ref()],
+        bump = bet_vault.bump
     )]
-    pub escrow_pda: UncheckedAccount<'info>,
+    pub bet_vault: Account<'info, BetVault>,
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct ReclaimUnsoldPick<'info> {
-    #[account(
-        seeds = [b"escrow", user_pick.key().as_ref()],
-        bump
-    )]
-    pub escrow_pda: UncheckedAccount<'info>,
+pub struct SweepUnclaimed<'info> {
+    #[account(mut, has_one = fixture)]
+    pub bet_pool: Account<'info, BetPool>,
+
+    pub fixture: Account<'info, Fixture>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
 
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = escrow_pda,
-        constraint = escrow_token_account.amount == 1 @ ErrorCode::InvalidTokenBalance,
-        constraint = escrow_token_account.delegate == COption::None @ ErrorCode::UnexpectedDelegate,
-        constraint = escrow_token_account.close_authority == COption::None @ ErrorCode::UnexpectedCloseAuthority,
+        seeds = [b"bet_vault", bet_pool.key().as_ref()],
+        bump = bet_vault.bump
     )]
-    pub escrow_token_account: Account<'info, TokenAccount>,
+    pub bet_vault: Account<'info, BetVault>,
 
+    // Either the treasury or the jackpot vault, per config
     #[account(mut)]
-    pub seller_token_account: Account<'info, TokenAccount>,
+    pub destination: UncheckedAccount<'info>,
+}
 
-    #[account(mut)]
-    pub user_pick: Account<'info, UserPick>,
+#[derive(Accounts)]
+pub struct AuditPool<'info> {
+    #[account(has_one = fee_vault)]
+    pub bet_pool: Account<'info, BetPool>,
 
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub fee_vault: Account<'info, FeeVault>,
 
-    #[account(mut)]
-    pub pool: Account<'info, BetPool>,
+    #[account(seeds = [b"bet_vault", bet_pool.key().as_ref()], bump = bet_vault.bump)]
+    pub bet_vault: Account<'info, BetVault>,
+}
 
+#[derive(Accounts)]
+pub struct InitializeCouncil<'info> {
     #[account(mut)]
-    pub seller: AccountInfo<'info>,
+    pub admin: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + std::mem::size_of::<Council>(),
+        seeds = [b"council"],
+        bump
+    )]
+    pub council: Account<'info, Council>,
+
+    pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct ProposeAction<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
 
+    #[account(mut, seeds = [b"council"], bump = council.bump)]
+    pub council: Account<'info, Council>,
 
-#[account]
-pub struct BetPool {
-    pub fixture_id: u64,
-    pub sport_name: [u8; 32],
-    pub player_id: Pubkey,
-    pub stat_name: [u8; 32],
-    pub stat_line: u32,
-    pub deadline: i64,
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + std::mem::size_of::<Proposal>(),
+        seeds = [b"proposal", council.key().as_ref(), &council.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
 
-    pub total_over_amount: u64,
-    pub total_under_amount: u64,
+    pub system_program: Program<'info, System>,
+}
 
-    pub fee_vault: Pubkey,
-    pub result_published: bool,
-    pub final_stat: u32,
+#[derive(Accounts)]
+pub struct ApproveAction<'info> {
+    pub member: Signer<'info>,
 
-    pub bump: u8,
+    #[account(seeds = [b"council"], bump = council.bump)]
+    pub council: Account<'info, Council>,
+
+    #[account(mut, has_one = council)]
+    pub proposal: Account<'info, Proposal>,
 }
 
+#[derive(Accounts)]
+pub struct ExecuteAction<'info> {
+    #[account(mut, seeds = [b"council"], bump = council.bump)]
+    pub council: Account<'info, Council>,
 
+    #[account(mut, has_one = council)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
 
-#[derive(Accounts)]
-pub struct SettleClaim<'info> {
     #[account(mut)]
-    pub user_pick: Account<'info, UserPick>,
+    pub bet_pool: Option<Account<'info, BetPool>>,
+
+    pub fixture: Option<Account<'info, Fixture>>,
+
+    pub sport: Option<Box<Account<'info, Sport>>>,
+
+    #[account(mut)]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+
+    #[account(mut)]
+    pub treasury: Option<SystemAccount<'info>>,
+
+    #[account(mut)]
+    pub jackpot_vault: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub recipient: Option<SystemAccount<'info>>,
+}
 
+#[derive(Accounts)]
+pub struct CloseBetPool<'info> {
     #[account(mut)]
+    pub admin: Signer<'info>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+
+    #[account(mut, close = admin, has_one = fixture, has_one = fee_vault)]
     pub bet_pool: Account<'info, BetPool>,
 
+    pub fixture: Account<'info, Fixture>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, address = config.treasury @ ErrorCode::InvalidTreasury)]
+    pub treasury: SystemAccount<'info>,
+
     #[account(mut)]
-    pub recipient: SystemAccount<'info>,
+    pub fee_vault: Account<'info, FeeVault>,
 
     #[account(
         mut,
+        close = admin,
         seeds = [b"bet_vault", bet_pool.key().as_ref()],
-        bump
+        bump = bet_vault.bump
     )]
-    pub bet_vault: AccountInfo<'info>,
+    pub bet_vault: Account<'info, BetVault>,
 
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,107 @@
+import { expect } from "chai";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+    BN,
+    Pool,
+    Slate,
+    admin,
+    attestationPda,
+    chainTime,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    exists,
+    expectError,
+    fund,
+    placeBet,
+    program,
+    sportPda,
+    waitUntil,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Compliance attestations", function () {
+    this.timeout(180000);
+
+    let slate: Slate;
+    let pool: Pool;
+
+    const code = (jurisdiction: string) => Array.from(Buffer.from(jurisdiction));
+
+    const setPoolCompliance = (target: Pool, jurisdictions: string[]) =>
+        program.methods
+            .setPoolCompliance(true, jurisdictions.map(code))
+            .accountsPartial({
+                betPool: target.betPool,
+                sport: sportPda,
+                authority: admin.publicKey,
+                roleGrant: null,
+            })
+            .rpc();
+
+    const issueAttestation = (wallet: PublicKey, jurisdiction: string, expiresAt: number) =>
+        program.methods
+            .issueAttestation(wallet, code(jurisdiction), new BN(expiresAt))
+            .accountsPartial({
+                authority: admin.publicKey,
+                roleGrant: null,
+                attestation: attestationPda(wallet),
+                systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+
+    const revokeAttestation = (wallet: PublicKey) =>
+        program.methods
+            .revokeAttestation()
+            .accountsPartial({ authority: admin.publicKey, roleGrant: null, attestation: attestationPda(wallet) })
+            .rpc();
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+        pool = await createPool(slate, 100);
+        await setPoolCompliance(pool, ["US", "CA"]);
+    });
+
+    it("Accepts bets from wallets attested in an allowed jurisdiction", async () => {
+        const bettor = await fund();
+        await issueAttestation(bettor.publicKey, "US", (await chainTime()) + 3600);
+
+        const attestation = await program.account.attestation.fetch(attestationPda(bettor.publicKey));
+        expect(attestation.wallet.toBase58()).to.equal(bettor.publicKey.toBase58());
+        expect(attestation.jurisdiction).to.deep.equal(code("US"));
+
+        await placeBet(pool, bettor, 50_000_000, true, { attestation: attestationPda(bettor.publicKey) });
+        const betPool = await program.account.betPool.fetch(pool.betPool);
+        expect(betPool.requiresAttestation).to.equal(true);
+        expect(betPool.jurisdictionCount).to.equal(2);
+        console.log("✅ US-attested wallet placed a bet");
+    });
+
+    it("Rejects missing, foreign, expired and revoked attestations", async () => {
+        const bettor = await fund();
+        await expectError(placeBet(pool, bettor, 50_000_000, true), "AttestationRequired");
+
+        await issueAttestation(bettor.publicKey, "FR", (await chainTime()) + 3600);
+        const attestation = attestationPda(bettor.publicKey);
+        await expectError(placeBet(pool, bettor, 50_000_000, true, { attestation }), "JurisdictionNotAllowed");
+
+        // Someone else's attestation doesn't count
+        const other = await fund();
+        await issueAttestation(other.publicKey, "US", (await chainTime()) + 3600);
+        await expectError(
+            placeBet(pool, bettor, 50_000_000, true, { attestation: attestationPda(other.publicKey) }),
+            "AttestationRequired"
+        );
+
+        await expectError(issueAttestation(bettor.publicKey, "US", (await chainTime()) - 1), "AttestationExpired");
+        const expiresAt = (await chainTime()) + 3;
+        await issueAttestation(bettor.publicKey, "US", expiresAt);
+        await waitUntil(expiresAt);
+        await expectError(placeBet(pool, bettor, 50_000_000, true, { attestation }), "AttestationExpired");
+
+        await revokeAttestation(other.publicKey);
+        expect(await exists(attestationPda(other.publicKey))).to.equal(false);
+        await expectError(placeBet(pool, other, 50_000_000, true), "AttestationRequired");
+        console.log("✅ Compliance gate held");
+    });
+});
@@ -0,0 +1,82 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+    Pool,
+    Slate,
+    admin,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    expectError,
+    finalize,
+    fund,
+    lockAndPublish,
+    netOf,
+    placeBet,
+    program,
+    provider,
+    settleClaim,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Pool audit", function () {
+    this.timeout(180000);
+
+    let slate: Slate;
+
+    const auditPool = (pool: Pool, feeVault: PublicKey = pool.feeVault) =>
+        program.methods
+            .auditPool()
+            .accountsPartial({ betPool: pool.betPool, feeVault, betVault: pool.betVault })
+            .view();
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+    });
+
+    it("Reports a fully reserved pool through its whole lifecycle", async () => {
+        const pool = await createPool(slate, 100);
+        const over = await fund();
+        const overPick = await placeBet(pool, over, 100_000_000, true);
+        await placeBet(pool, await fund(), 100_000_000, false);
+
+        const open = await auditPool(pool);
+        expect(open.solvent).to.equal(true);
+        expect(open.feeVaultShared).to.equal(false);
+        expect(open.betVaultExpected.toNumber()).to.equal(2 * netOf(100_000_000));
+        expect(open.betVaultActual.toNumber()).to.equal(open.betVaultExpected.toNumber());
+        expect(open.betVaultDiscrepancy.toNumber()).to.equal(0);
+        expect(open.feeVaultExpected.toNumber()).to.equal(2 * (100_000_000 - netOf(100_000_000)));
+        expect(open.feeVaultDiscrepancy.toNumber()).to.equal(0);
+
+        await lockAndPublish(pool, 150);
+        await finalize(pool);
+        await settleClaim(pool, overPick, over);
+
+        const settled = await auditPool(pool);
+        expect(settled.solvent).to.equal(true);
+        expect(settled.betVaultExpected.toNumber()).to.equal(0);
+        expect(settled.betVaultDiscrepancy.toNumber()).to.equal(0);
+        console.log("✅ Audit balanced before and after settlement");
+    });
+
+    it("Surfaces surpluses and refuses a fee vault from another pool", async () => {
+        const pool = await createPool(slate, 200);
+        const other = await createPool(slate, 300);
+        await placeBet(pool, await fund(), 100_000_000, true);
+
+        // Lamports sent straight to the vault show up as a positive discrepancy
+        await provider.sendAndConfirm(
+            new anchor.web3.Transaction().add(
+                SystemProgram.transfer({ fromPubkey: admin.publicKey, toPubkey: pool.betVault, lamports: 12_345 })
+            )
+        );
+        const report = await auditPool(pool);
+        expect(report.solvent).to.equal(true);
+        expect(report.betVaultDiscrepancy.toNumber()).to.equal(12_345);
+
+        await expectError(auditPool(pool, other.feeVault), "ConstraintHasOne");
+        console.log("✅ Surplus of 12345 reported");
+    });
+});
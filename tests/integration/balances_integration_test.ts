@@ -0,0 +1,121 @@
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+    BN,
+    Slate,
+    balancePda,
+    blockPda,
+    buyPick,
+    configPda,
+    connection,
+    createPool,
+    createSlate,
+    deposit,
+    ensureProtocol,
+    expectError,
+    finalize,
+    fund,
+    listPick,
+    lockAndPublish,
+    netOf,
+    placeBet,
+    program,
+    settleClaim,
+    vaultExcess,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Balances", function () {
+    this.timeout(180000);
+
+    let slate: Slate;
+
+    const withdraw = (owner: Keypair, amount: number) =>
+        program.methods
+            .withdraw(new BN(amount))
+            .accountsPartial({
+                owner: owner.publicKey,
+                ownerBlock: blockPda(owner.publicKey),
+                balance: balancePda(owner.publicKey),
+            })
+            .signers([owner])
+            .rpc();
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+    });
+
+    it("Round-trips deposit → bet → settle to balance → withdraw", async () => {
+        const pool = await createPool(slate, 500);
+        const bettor = await fund();
+        const opponent = await fund();
+        const deposited = 1_000_000_000;
+        const stake = 400_000_000;
+
+        const balance = balancePda(bettor.publicKey);
+        await deposit(bettor, deposited);
+        console.log("💰 Deposited", deposited);
+
+        const pick = await placeBet(pool, bettor, stake, true, { balance });
+        await placeBet(pool, opponent, stake, false);
+        expect((await program.account.balance.fetch(balance)).amount.toNumber()).to.equal(deposited - stake);
+
+        await lockAndPublish(pool, 550);
+        await finalize(pool);
+
+        await settleClaim(pool, pick, bettor, { balance });
+        const payout = 2 * netOf(stake);
+        const credited = await program.account.balance.fetch(balance);
+        expect(credited.amount.toNumber()).to.equal(deposited - stake + payout);
+        expect(await vaultExcess(pool.betVault)).to.equal(0);
+
+        const amount = credited.amount.toNumber();
+
+        // Nothing beyond the credited amount can leave the balance
+        await expectError(withdraw(bettor, amount + 1), "InsufficientBalance");
+
+        const walletBefore = await connection.getBalance(bettor.publicKey);
+        await withdraw(bettor, amount);
+
+        const drained = await program.account.balance.fetch(balance);
+        expect(drained.amount.toNumber()).to.equal(0);
+        expect(drained.totalDeposited.toNumber()).to.equal(deposited);
+        expect(drained.totalWithdrawn.toNumber()).to.equal(amount);
+        expect((await connection.getBalance(bettor.publicKey)) - walletBefore).to.equal(amount);
+        expect(await vaultExcess(balance)).to.equal(0);
+        console.log("✅ Withdrew", amount, "after settling into the balance");
+    });
+
+    it("Settles marketplace sales balance to balance", async () => {
+        const pool = await createPool(slate, 600);
+        const seller = await fund();
+        const buyer = await fund();
+        const price = 80_000_000;
+
+        await deposit(seller, 10_000_000);
+        await deposit(buyer, 200_000_000);
+        const sellerBalance = balancePda(seller.publicKey);
+        const buyerBalance = balancePda(buyer.publicKey);
+
+        const pick = await placeBet(pool, seller, 50_000_000, true);
+        await listPick(pool, pick, seller);
+
+        // The same ledger on both sides would credit the price without debiting it
+        await expectError(
+            buyPick(pool, pick, seller.publicKey, buyer, price, { buyer: buyerBalance, seller: buyerBalance }),
+            "DuplicateBalance"
+        );
+
+        await buyPick(pool, pick, seller.publicKey, buyer, price, { buyer: buyerBalance, seller: sellerBalance });
+
+        const { royaltyBps } = await program.account.config.fetch(configPda);
+        const royalty = Math.floor((price * royaltyBps) / 10_000);
+        expect((await program.account.balance.fetch(buyerBalance)).amount.toNumber()).to.equal(200_000_000 - price);
+        expect((await program.account.balance.fetch(sellerBalance)).amount.toNumber()).to.equal(
+            10_000_000 + price - royalty
+        );
+        expect(await vaultExcess(buyerBalance)).to.equal(0);
+        expect(await vaultExcess(sellerBalance)).to.equal(0);
+        console.log("✅ Sale of", price, "moved between balances less", royalty, "royalty");
+    });
+});
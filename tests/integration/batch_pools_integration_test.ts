@@ -0,0 +1,112 @@
+import { expect } from "chai";
+import { AccountMeta, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+    STAT,
+    Pool,
+    Slate,
+    admin,
+    configPda,
+    createPlayer,
+    createSlate,
+    ensureProtocol,
+    expectError,
+    fund,
+    name,
+    pda,
+    placeBet,
+    poolPda,
+    program,
+    sportPda,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Batch pool creation", function () {
+    this.timeout(180000);
+
+    let slate: Slate;
+    let feeVault: PublicKey;
+
+    const batchPool = (player: PublicKey, statLine: number): Pool => {
+        const betPool = poolPda({ ...slate, player }, statLine);
+        return {
+            ...slate,
+            player,
+            statName: STAT,
+            statLine,
+            betPool,
+            feeVault,
+            betVault: pda(Buffer.from("bet_vault"), betPool.toBuffer()),
+        };
+    };
+
+    // Each entry supplies [bet_pool, bet_vault, player]
+    const batchAccounts = (pools: Pool[]): AccountMeta[] =>
+        pools.flatMap((pool) => [
+            { pubkey: pool.betPool, isWritable: true, isSigner: false },
+            { pubkey: pool.betVault, isWritable: true, isSigner: false },
+            { pubkey: pool.player, isWritable: false, isSigner: false },
+        ]);
+
+    const createBatch = (pools: Pool[], remainingAccounts = batchAccounts(pools)) =>
+        program.methods
+            .initializeBetPoolsBatch(
+                pools.map((pool) => ({
+                    playerId: pool.player,
+                    statName: name(pool.statName),
+                    statLine: pool.statLine,
+                }))
+            )
+            .accountsPartial({
+                admin: admin.publicKey,
+                roleGrant: null,
+                fixture: slate.fixture,
+                sport: sportPda,
+                config: configPda,
+                feeVault,
+                systemProgram: SystemProgram.programId,
+            })
+            .remainingAccounts(remainingAccounts)
+            .rpc();
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+        feeVault = pda(Buffer.from("fee_vault"), slate.fixture.toBuffer());
+    });
+
+    it("Creates a slate of pools sharing one fee vault", async () => {
+        const secondPlayer = await createPlayer();
+        const pools = [batchPool(slate.player, 100), batchPool(slate.player, 150), batchPool(secondPlayer, 100)];
+        await createBatch(pools);
+
+        for (const pool of pools) {
+            const betPool = await program.account.betPool.fetch(pool.betPool);
+            expect(betPool.statLine).to.equal(pool.statLine);
+            expect(betPool.playerId.toBase58()).to.equal(pool.player.toBase58());
+            expect(betPool.feeVault.toBase58()).to.equal(feeVault.toBase58());
+            expect((await program.account.betVault.fetch(pool.betVault)).owner.toBase58()).to.equal(
+                pool.betPool.toBase58()
+            );
+        }
+        expect((await program.account.feeVault.fetch(feeVault)).owner.toBase58()).to.equal(
+            slate.fixture.toBase58()
+        );
+
+        // Batch-created pools take bets like any other
+        await placeBet(pools[2], await fund(), 50_000_000, true);
+        expect((await program.account.feeVault.fetch(feeVault)).totalDeposited.toNumber()).to.equal(2_500_000);
+        console.log("✅ Created", pools.length, "pools in one instruction");
+    });
+
+    it("Rejects empty and misaligned batches", async () => {
+        await expectError(createBatch([], []), "EmptyBatch");
+
+        const pools = [batchPool(slate.player, 200), batchPool(slate.player, 250)];
+        await expectError(createBatch(pools, batchAccounts(pools).slice(0, 5)), "BatchAccountsMismatch");
+
+        // A pool address that doesn't match its spec is refused
+        const swapped = batchAccounts(pools);
+        [swapped[0], swapped[3]] = [swapped[3], swapped[0]];
+        await expectError(createBatch(pools, swapped), "InvalidPda");
+        console.log("✅ Malformed batches rejected");
+    });
+});
@@ -0,0 +1,139 @@
+import { expect } from "chai";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { PublicKey } from "@solana/web3.js";
+import {
+    Slate,
+    admin,
+    blockPda,
+    blockWallet,
+    buyPick,
+    connection,
+    crankAccounts,
+    crankSettle,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    exists,
+    expectError,
+    finalize,
+    fund,
+    listPick,
+    lockAndPublish,
+    placeBet,
+    profilePda,
+    program,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Blocklist and marketplace", function () {
+    this.timeout(180000);
+
+    let slate: Slate;
+
+    const unblockWallet = (wallet: PublicKey) =>
+        program.methods
+            .unblockWallet()
+            .accountsPartial({
+                authority: admin.publicKey,
+                roleGrant: null,
+                wallet,
+                blockedWallet: blockPda(wallet),
+            })
+            .rpc();
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+    });
+
+    it("Cranks a mixed batch of winners, losers and a blocked holder", async () => {
+        const pool = await createPool(slate, 300);
+        const winner = await fund();
+        const blockedWinner = await fund();
+        const loser = await fund();
+
+        const picks = [
+            await placeBet(pool, winner, 100_000_000, true),
+            await placeBet(pool, blockedWinner, 100_000_000, true),
+            await placeBet(pool, loser, 200_000_000, false),
+        ];
+
+        const blockedPda = blockPda(blockedWinner.publicKey);
+        await blockWallet(blockedWinner.publicKey, 7);
+        console.log("⛔ Blocked", blockedWinner.publicKey.toBase58());
+
+        await lockAndPublish(pool, 350);
+        await finalize(pool);
+
+        const winnerBefore = await connection.getBalance(winner.publicKey);
+        const blockedBefore = await connection.getBalance(blockedWinner.publicKey);
+        const heldBefore = await connection.getBalance(blockedPda);
+
+        await crankSettle(pool, picks.flatMap((pick) => crankAccounts(pick)));
+
+        const betPool = await program.account.betPool.fetch(pool.betPool);
+        const pot = betPool.totalOverAmount.add(betPool.totalUnderAmount).toNumber();
+        const share = pot / 2;
+        expect(betPool.settledCount.toNumber()).to.equal(3);
+        expect(betPool.totalPaidOut.toNumber()).to.equal(pot);
+
+        expect((await connection.getBalance(winner.publicKey)) - winnerBefore).to.equal(share);
+
+        // The blocked holder's winnings are parked in its block PDA
+        expect(await connection.getBalance(blockedWinner.publicKey)).to.equal(blockedBefore);
+        expect((await connection.getBalance(blockedPda)) - heldBefore).to.equal(share);
+        const blocked = await program.account.blockedWallet.fetch(blockedPda);
+        expect(blocked.heldAmount.toNumber()).to.equal(share);
+
+        for (const pick of picks) {
+            expect((await program.account.userPick.fetch(pick.userPick)).claimed).to.equal(true);
+        }
+        const loserProfile = await program.account.userProfile.fetch(profilePda(loser.publicKey));
+        expect(loserProfile.lossCount).to.equal(1);
+        const blockedProfile = await program.account.userProfile.fetch(profilePda(blockedWinner.publicKey));
+        expect(blockedProfile.winCount).to.equal(1);
+        expect(blockedProfile.totalWon.toNumber()).to.equal(share);
+
+        // Unblocking releases the held winnings and closes the block PDA
+        await unblockWallet(blockedWinner.publicKey);
+        expect((await connection.getBalance(blockedWinner.publicKey)) - blockedBefore).to.equal(share);
+        expect(await exists(blockedPda)).to.equal(false);
+        console.log("✅ Cranked 3 picks, held", share, "for the blocked holder until unblocked");
+    });
+
+    it("Keeps blocked wallets out of betting and the marketplace", async () => {
+        const pool = await createPool(slate, 400);
+        const blocked = await fund();
+        await blockWallet(blocked.publicKey);
+        await expectError(placeBet(pool, blocked, 50_000_000, true), "WalletBlocked");
+
+        const seller = await fund();
+        const pick = await placeBet(pool, seller, 50_000_000, true);
+        await listPick(pool, pick, seller);
+        await expectError(buyPick(pool, pick, seller.publicKey, blocked, 60_000_000), "WalletBlocked");
+
+        await unblockWallet(blocked.publicKey);
+        await buyPick(pool, pick, seller.publicKey, blocked, 60_000_000);
+        const bought = await program.account.userPick.fetch(pick.userPick);
+        expect(bought.owner.toBase58()).to.equal(blocked.publicKey.toBase58());
+        expect(bought.forSale).to.equal(false);
+        const tokenAccount = getAssociatedTokenAddressSync(pick.mint, blocked.publicKey);
+        expect((await connection.getTokenAccountBalance(tokenAccount)).value.amount).to.equal("1");
+        console.log("✅ Unblocked buyer completed the purchase");
+    });
+
+    it("Ties listings to the pick's own mint and only sells listed picks", async () => {
+        const pool = await createPool(slate, 500);
+        const seller = await fund();
+        const buyer = await fund();
+        const first = await placeBet(pool, seller, 50_000_000, true);
+        const second = await placeBet(pool, seller, 50_000_000, false);
+
+        // Escrowing another pick's NFT under this pick would let the seller sell it twice
+        await expectError(listPick(pool, first, seller, second.mint), "ConstraintHasOne");
+        await expectError(buyPick(pool, first, seller.publicKey, buyer, 60_000_000), "NotListedForSale");
+
+        await listPick(pool, first, seller);
+        await expectError(listPick(pool, first, seller), "AlreadyListed");
+        console.log("✅ Forged and repeat listings rejected");
+    });
+});
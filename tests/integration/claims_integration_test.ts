@@ -0,0 +1,120 @@
+import { expect } from "chai";
+import { PublicKey } from "@solana/web3.js";
+import {
+    BN,
+    Pool,
+    Slate,
+    admin,
+    closePool,
+    configPda,
+    connection,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    executeCouncilAction,
+    expectError,
+    finalize,
+    fund,
+    jackpotVaultPda,
+    lockAndPublish,
+    netOf,
+    placeBet,
+    program,
+    settleClaim,
+    updateConfig,
+    vaultExcess,
+    waitUntil,
+    withdrawFees,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Claim deadline and sweeps", function () {
+    this.timeout(180000);
+
+    // Long enough to check the period is enforced, short enough to wait out
+    const SHORT_CLAIM_PERIOD = 8;
+
+    let slate: Slate;
+
+    const sweep = (pool: Pool, destination: PublicKey) =>
+        program.methods
+            .sweepUnclaimed()
+            .accountsPartial({
+                betPool: pool.betPool,
+                fixture: pool.fixture,
+                config: configPda,
+                betVault: pool.betVault,
+                destination,
+            })
+            .rpc();
+
+    const withdrawJackpot = (recipient: PublicKey, amount: number) =>
+        executeCouncilAction(
+            { withdrawJackpot: { recipient, amount: new BN(amount) } },
+            { jackpotVault: jackpotVaultPda, recipient }
+        );
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+    });
+
+    afterEach(async () => {
+        await updateConfig();
+    });
+
+    it("Sweeps unclaimed winnings to the treasury after the deadline", async () => {
+        const pool = await createPool(slate, 100);
+        const over = await fund();
+        const overPick = await placeBet(pool, over, 100_000_000, true);
+        await placeBet(pool, await fund(), 100_000_000, false);
+
+        await updateConfig({ claimPeriod: new BN(SHORT_CLAIM_PERIOD) });
+        await lockAndPublish(pool, 150);
+        await finalize(pool);
+        await expectError(sweep(pool, admin.publicKey), "ClaimPeriodActive");
+
+        const { claimDeadline } = await program.account.betPool.fetch(pool.betPool);
+        await waitUntil(claimDeadline.toNumber());
+
+        await expectError(settleClaim(pool, overPick, over), "ClaimDeadlinePassed");
+        // Config says treasury, so the jackpot can't take the sweep
+        await expectError(sweep(pool, jackpotVaultPda), "InvalidUnclaimedDestination");
+
+        await sweep(pool, admin.publicKey);
+        const betPool = await program.account.betPool.fetch(pool.betPool);
+        expect(betPool.unclaimedSwept.toNumber()).to.equal(2 * netOf(100_000_000));
+        expect(await vaultExcess(pool.betVault)).to.equal(0);
+
+        // Swept pools close even though their picks were never settled
+        await withdrawFees(pool, betPool.totalFeesCollected.toNumber());
+        await closePool(pool);
+        console.log("✅ Swept", betPool.unclaimedSwept.toNumber(), "to the treasury");
+    });
+
+    it("Rolls unclaimed winnings into the jackpot and pays it out through the council", async () => {
+        const pool = await createPool(slate, 200);
+        await placeBet(pool, await fund(), 100_000_000, true);
+        await placeBet(pool, await fund(), 100_000_000, false);
+
+        await updateConfig({ claimPeriod: new BN(SHORT_CLAIM_PERIOD), unclaimedDestination: { jackpot: {} } });
+        await lockAndPublish(pool, 250);
+        await finalize(pool);
+
+        const { claimDeadline } = await program.account.betPool.fetch(pool.betPool);
+        await waitUntil(claimDeadline.toNumber());
+
+        const jackpotBefore = await vaultExcess(jackpotVaultPda);
+        await sweep(pool, jackpotVaultPda);
+        const swept = (await vaultExcess(jackpotVaultPda)) - jackpotBefore;
+        expect(swept).to.equal(2 * netOf(100_000_000));
+
+        const winner = await fund(1_000_000);
+        await expectError(withdrawJackpot(winner.publicKey, jackpotBefore + swept + 1), "VaultOverdrawn");
+
+        const winnerBefore = await connection.getBalance(winner.publicKey);
+        await withdrawJackpot(winner.publicKey, swept);
+        expect((await connection.getBalance(winner.publicKey)) - winnerBefore).to.equal(swept);
+        expect(await vaultExcess(jackpotVaultPda)).to.equal(jackpotBefore);
+        console.log("✅ Jackpot took", swept, "and paid it out");
+    });
+});
@@ -0,0 +1,70 @@
+import { expect } from "chai";
+import {
+    Slate,
+    closePool,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    exists,
+    expectError,
+    finalize,
+    fund,
+    lockAndPublish,
+    placeBet,
+    program,
+    settleClaim,
+    withdrawFees,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Closing pools", function () {
+    this.timeout(180000);
+
+    let slate: Slate;
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+    });
+
+    it("Closes a fully settled pool once its fees are withdrawn", async () => {
+        const pool = await createPool(slate, 100);
+        const over = await fund();
+        const under = await fund();
+        const overPick = await placeBet(pool, over, 100_000_000, true);
+        const underPick = await placeBet(pool, under, 100_000_000, false);
+
+        await lockAndPublish(pool, 150);
+        await finalize(pool);
+        await settleClaim(pool, overPick, over);
+        await settleClaim(pool, underPick, under);
+
+        // Fees leave only through the council, never as a side effect of closing
+        await expectError(closePool(pool), "FeesNotWithdrawn");
+
+        const fees = (await program.account.betPool.fetch(pool.betPool)).totalFeesCollected.toNumber();
+        await withdrawFees(pool, fees);
+        await closePool(pool);
+
+        expect(await exists(pool.betPool)).to.equal(false);
+        expect(await exists(pool.betVault)).to.equal(false);
+        expect(await exists(pool.feeVault)).to.equal(false);
+        console.log("✅ Closed pool after withdrawing", fees, "in fees");
+    });
+
+    it("Refuses to close unsettled pools", async () => {
+        const pool = await createPool(slate, 200);
+        const over = await fund();
+        await placeBet(pool, over, 100_000_000, true);
+        await placeBet(pool, await fund(), 100_000_000, false);
+
+        await expectError(closePool(pool), "PoolNotSettled");
+
+        await lockAndPublish(pool, 250);
+        await finalize(pool);
+
+        // One pick is still unclaimed and its claim window is open
+        await expectError(closePool(pool), "PoolNotClosable");
+        expect(await exists(pool.betPool)).to.equal(true);
+        console.log("✅ Pool with open claims stays open");
+    });
+});
@@ -0,0 +1,82 @@
+import { expect } from "chai";
+import {
+    PASS_RUSH,
+    RUSHING,
+    STAT,
+    Slate,
+    connection,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    expectError,
+    finalize,
+    fund,
+    lockPool,
+    netOf,
+    placeBet,
+    program,
+    publishComposite,
+    publishResult,
+    setStatComponents,
+    settleClaim,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Composite props", function () {
+    this.timeout(180000);
+
+    let slate: Slate;
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+    });
+
+    it("Settles a passing + rushing yards prop from its components", async () => {
+        const pool = await createPool(slate, 250, PASS_RUSH);
+        await setStatComponents(pool, [
+            [STAT, 100],
+            [RUSHING, 100],
+        ]);
+
+        const over = await fund();
+        const under = await fund();
+        const overPick = await placeBet(pool, over, 100_000_000, true);
+        await placeBet(pool, under, 100_000_000, false);
+
+        await lockPool(pool);
+        await publishComposite(pool, [180, 90]);
+
+        const betPool = await program.account.betPool.fetch(pool.betPool);
+        expect(betPool.statComponentCount).to.equal(2);
+        expect(betPool.componentValues.slice(0, 2)).to.deep.equal([180, 90]);
+        expect(betPool.finalStat).to.equal(270);
+
+        await finalize(pool);
+        const overBefore = await connection.getBalance(over.publicKey);
+        await settleClaim(pool, overPick, over);
+        expect((await connection.getBalance(over.publicKey)) - overBefore).to.equal(2 * netOf(100_000_000));
+        console.log("✅ Composite 180 + 90 settled over the 250 line");
+    });
+
+    it("Rejects single-value results and late component changes", async () => {
+        const pool = await createPool(slate, 260, PASS_RUSH);
+
+        await expectError(setStatComponents(pool, [["NOT_A_STAT", 100]]), "InvalidStatType");
+        await setStatComponents(pool, [
+            [STAT, 100],
+            [RUSHING, 100],
+        ]);
+        await placeBet(pool, await fund(), 50_000_000, true);
+
+        // Components are fixed once money is in the pool
+        await expectError(setStatComponents(pool, [[STAT, 100]]), "PoolAlreadyActive");
+
+        await lockPool(pool);
+        await expectError(publishResult(pool, 300), "CompositeResultRequired");
+
+        const plain = await createPool(slate, 270);
+        await lockPool(plain);
+        await expectError(publishComposite(plain, [180, 90]), "NotCompositePool");
+        console.log("✅ Composite pools only take component results");
+    });
+});
@@ -0,0 +1,75 @@
+import { expect } from "chai";
+import {
+    BN,
+    Slate,
+    councilPda,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    expectError,
+    finalize,
+    finalizeNow,
+    fund,
+    lockAndPublish,
+    placeBet,
+    program,
+    publishResult,
+    updateConfig,
+    waitUntil,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Stat corrections", function () {
+    this.timeout(180000);
+
+    let slate: Slate;
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+    });
+
+    afterEach(async () => {
+        await updateConfig();
+    });
+
+    it("Amends a result inside the correction window and logs the change", async () => {
+        const pool = await createPool(slate, 100);
+        await placeBet(pool, await fund(), 100_000_000, true);
+        await placeBet(pool, await fund(), 100_000_000, false);
+
+        await lockAndPublish(pool, 95);
+        await publishResult(pool, 120);
+
+        const amended = await program.account.betPool.fetch(pool.betPool);
+        expect(amended.finalStat).to.equal(120);
+        expect(amended.amendmentCount).to.equal(1);
+        expect(amended.amendments[0].previousStat).to.equal(95);
+        expect(amended.amendments[0].newStat).to.equal(120);
+        expect(amended.amendments[0].authority.toBase58()).to.equal(councilPda.toBase58());
+
+        await waitUntil(amended.publishedAt.toNumber() + amended.correctionWindow.toNumber());
+        await expectError(publishResult(pool, 130), "CorrectionWindowClosed");
+
+        await finalize(pool);
+        expect((await program.account.betPool.fetch(pool.betPool)).finalStat).to.equal(120);
+        console.log("✅ Corrected 95 → 120 before finalizing");
+    });
+
+    it("Holds finalization until the correction window closes", async () => {
+        // Pools snapshot the window at creation, so this one outlasts the dispute window
+        await updateConfig({ correctionWindow: new BN(14) });
+        const pool = await createPool(slate, 200);
+        await placeBet(pool, await fund(), 100_000_000, true);
+        await placeBet(pool, await fund(), 100_000_000, false);
+        await lockAndPublish(pool, 210);
+
+        const proposed = await program.account.betPool.fetch(pool.betPool);
+        expect(proposed.correctionWindow.toNumber()).to.equal(14);
+        await waitUntil(proposed.disputeWindowEnd.toNumber());
+        await expectError(finalizeNow(pool), "CorrectionWindowOpen");
+
+        await finalize(pool);
+        expect((await program.account.betPool.fetch(pool.betPool)).status).to.deep.equal({ finalized: {} });
+        console.log("✅ Finalized only after the correction window");
+    });
+});
@@ -0,0 +1,107 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+    admin,
+    configPda,
+    councilPda,
+    ensureProtocol,
+    executeCouncilAction,
+    expectError,
+    pda,
+    program,
+    u64,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Council", function () {
+    this.timeout(180000);
+
+    const DEFAULT_ROYALTY_BPS = 250;
+    const second = Keypair.generate();
+
+    const updateCouncil = (members: PublicKey[], threshold: number) => ({
+        updateCouncil: {
+            members: [...members, ...new Array(10 - members.length).fill(PublicKey.default)],
+            memberCount: members.length,
+            threshold,
+        },
+    });
+
+    const propose = async (action: object): Promise<PublicKey> => {
+        const council = await program.account.council.fetch(councilPda);
+        const proposal = pda(Buffer.from("proposal"), councilPda.toBuffer(), u64(council.proposalCount));
+        await program.methods
+            .proposeAction(action)
+            .accountsPartial({
+                proposer: admin.publicKey,
+                council: councilPda,
+                proposal,
+                systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+        return proposal;
+    };
+
+    const approve = (proposal: PublicKey, member: Keypair) =>
+        program.methods
+            .approveAction()
+            .accountsPartial({ member: member.publicKey, council: councilPda, proposal })
+            .signers([member])
+            .rpc();
+
+    const execute = (proposal: PublicKey) =>
+        program.methods
+            .executeAction()
+            .accountsPartial({
+                council: councilPda,
+                proposal,
+                config: configPda,
+                betPool: null,
+                fixture: null,
+                sport: null,
+                feeVault: null,
+                treasury: null,
+                jackpotVault: null,
+                recipient: null,
+            })
+            .rpc();
+
+    const royaltyBps = async () => (await program.account.config.fetch(configPda)).royaltyBps;
+
+    before(async () => {
+        await ensureProtocol();
+    });
+
+    it("Needs every approval the threshold asks for", async () => {
+        await executeCouncilAction(updateCouncil([admin.publicKey, second.publicKey], 2));
+
+        const proposal = await propose({ setRoyalty: { royaltyBps: 300 } });
+        await expectError(execute(proposal), "InsufficientApprovals");
+        await expectError(approve(proposal, Keypair.generate()), "NotCouncilMember");
+
+        await approve(proposal, second);
+        await expectError(approve(proposal, second), "AlreadyApproved");
+        await execute(proposal);
+        expect(await royaltyBps()).to.equal(300);
+        await expectError(execute(proposal), "ProposalExecuted");
+        console.log("✅ 2-of-2 council set the royalty to 300 bps");
+    });
+
+    it("Invalidates open proposals when the council rotates", async () => {
+        const stale = await propose({ setRoyalty: { royaltyBps: 400 } });
+
+        // Back to the single-member council the other suites rely on
+        const rotation = await propose(updateCouncil([admin.publicKey], 1));
+        await approve(rotation, second);
+        await execute(rotation);
+
+        await expectError(approve(stale, second), "NotCouncilMember");
+        await expectError(execute(stale), "ProposalStale");
+        await expectError(executeCouncilAction({ setRoyalty: { royaltyBps: 10_001 } }), "InvalidRoyalty");
+
+        await executeCouncilAction({ setRoyalty: { royaltyBps: DEFAULT_ROYALTY_BPS } });
+        expect(await royaltyBps()).to.equal(DEFAULT_ROYALTY_BPS);
+        const council = await program.account.council.fetch(councilPda);
+        expect(council.threshold).to.equal(1);
+        console.log("✅ Stale proposal rejected after rotation to epoch", council.epoch.toString());
+    });
+});
@@ -0,0 +1,92 @@
+import { expect } from "chai";
+import {
+    CRANK_TIP,
+    Slate,
+    blockPda,
+    connection,
+    crankAccounts,
+    crankSettle,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    expectError,
+    finalize,
+    fund,
+    lockAndPublish,
+    placeBet,
+    profilePda,
+    program,
+    vaultExcess,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Settlement crank", function () {
+    this.timeout(180000);
+
+    let slate: Slate;
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+    });
+
+    it("Settles a batch of winners and losers and tips the cranker from fees", async () => {
+        const pool = await createPool(slate, 100);
+        const winners = [await fund(), await fund()];
+        const loser = await fund();
+        const picks = [
+            await placeBet(pool, winners[0], 100_000_000, true),
+            await placeBet(pool, winners[1], 100_000_000, true),
+            await placeBet(pool, loser, 200_000_000, false),
+        ];
+
+        await lockAndPublish(pool, 150);
+        await finalize(pool);
+
+        const winnersBefore = await Promise.all(winners.map((w) => connection.getBalance(w.publicKey)));
+        const feesBefore = await vaultExcess(pool.feeVault);
+        await crankSettle(pool, picks.flatMap((pick) => crankAccounts(pick)));
+
+        const betPool = await program.account.betPool.fetch(pool.betPool);
+        const pot = betPool.totalOverAmount.add(betPool.totalUnderAmount).toNumber();
+        expect(betPool.settledCount.toNumber()).to.equal(3);
+        expect(betPool.totalPaidOut.toNumber()).to.equal(pot);
+        for (let i = 0; i < winners.length; i++) {
+            expect((await connection.getBalance(winners[i].publicKey)) - winnersBefore[i]).to.equal(pot / 2);
+        }
+        expect(feesBefore - (await vaultExcess(pool.feeVault))).to.equal(3 * CRANK_TIP);
+        expect((await program.account.userProfile.fetch(profilePda(loser.publicKey))).lossCount).to.equal(1);
+
+        // Re-cranking settled picks is a no-op rather than an error
+        await crankSettle(pool, picks.flatMap((pick) => crankAccounts(pick)));
+        expect((await program.account.betPool.fetch(pool.betPool)).settledCount.toNumber()).to.equal(3);
+        console.log("✅ Cranked 3 picks for a", 3 * CRANK_TIP, "lamport tip");
+    });
+
+    it("Rejects empty, misaligned and mismatched batches", async () => {
+        const pool = await createPool(slate, 200);
+        const winner = await fund();
+        const other = await fund();
+        const pick = await placeBet(pool, winner, 100_000_000, true);
+        await placeBet(pool, other, 100_000_000, false);
+        await lockAndPublish(pool, 250);
+        await finalize(pool);
+
+        await expectError(crankSettle(pool, []), "EmptyCrank");
+        await expectError(crankSettle(pool, crankAccounts(pick).slice(0, 4)), "BatchAccountsMismatch");
+
+        // The token account must belong to the holder being paid
+        const [userPick, tokenAccount] = crankAccounts(pick);
+        await expectError(
+            crankSettle(pool, [
+                userPick,
+                tokenAccount,
+                { pubkey: other.publicKey, isWritable: true, isSigner: false },
+                { pubkey: blockPda(other.publicKey), isWritable: true, isSigner: false },
+                { pubkey: profilePda(other.publicKey), isWritable: true, isSigner: false },
+            ]),
+            "NotPickHolder"
+        );
+        expect((await program.account.userPick.fetch(pick.userPick)).claimed).to.equal(false);
+        console.log("✅ Malformed crank batches rejected");
+    });
+});
@@ -0,0 +1,184 @@
+import { expect } from "chai";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import {
+    DISPUTE_BOND,
+    DISPUTE_TIMEOUT,
+    PASS_RUSH,
+    RUSHING,
+    STAT,
+    Pool,
+    Slate,
+    admin,
+    configPda,
+    connection,
+    createPool,
+    createSlate,
+    disputePda,
+    ensureProtocol,
+    exists,
+    expectError,
+    finalize,
+    finalizeNow,
+    fund,
+    insuranceFundPda,
+    lockAndPublish,
+    lockPool,
+    placeBet,
+    program,
+    publishComposite,
+    setStatComponents,
+    settleClaim,
+    sleep,
+    sportPda,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Disputes", function () {
+    this.timeout(180000);
+
+    let slate: Slate;
+
+    const openDispute = (pool: Pool, disputer: Keypair, proposedStat: number) =>
+        program.methods
+            .disputeResult(proposedStat)
+            .accountsPartial({
+                disputer: disputer.publicKey,
+                betPool: pool.betPool,
+                sport: sportPda,
+                config: configPda,
+                dispute: disputePda(pool.betPool),
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([disputer])
+            .rpc();
+
+    const resolveDispute = (pool: Pool, disputer: Keypair, upheld: boolean, arbitrator?: Keypair) => {
+        const builder = program.methods
+            .resolveDispute(upheld)
+            .accountsPartial({
+                arbitrator: arbitrator?.publicKey ?? admin.publicKey,
+                betPool: pool.betPool,
+                config: configPda,
+                dispute: disputePda(pool.betPool),
+                disputer: disputer.publicKey,
+                insuranceFund: insuranceFundPda,
+            });
+        return (arbitrator ? builder.signers([arbitrator]) : builder).rpc();
+    };
+
+    const expireDispute = (pool: Pool, disputer: Keypair) =>
+        program.methods
+            .expireDispute()
+            .accountsPartial({
+                betPool: pool.betPool,
+                config: configPda,
+                dispute: disputePda(pool.betPool),
+                disputer: disputer.publicKey,
+            })
+            .rpc();
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+    });
+
+    it("Upholds a dispute, then finalizes the corrected result", async () => {
+        const pool = await createPool(slate, 200);
+        const over = await fund();
+        const under = await fund();
+        const disputer = await fund();
+
+        const overPick = await placeBet(pool, over, 100_000_000, true);
+        await placeBet(pool, under, 100_000_000, false);
+
+        // The council publishes a wrong stat that would pay the under side
+        await lockAndPublish(pool, 180);
+
+        await expectError(openDispute(pool, disputer, 180), "DisputeMatchesResult");
+        await openDispute(pool, disputer, 230);
+        console.log("⚖️ Dispute opened");
+
+        // Finalization is blocked while the dispute is open
+        await expectError(finalizeNow(pool), "Dispute");
+        // Only the configured arbitrator rules on it
+        await expectError(resolveDispute(pool, disputer, true, await fund(10_000_000)), "Unauthorized");
+
+        const disputerBefore = await connection.getBalance(disputer.publicKey);
+        await resolveDispute(pool, disputer, true);
+
+        // The bond comes back with the dispute account's rent
+        expect(await connection.getBalance(disputer.publicKey)).to.be.greaterThan(
+            disputerBefore + DISPUTE_BOND - 1
+        );
+        expect(await exists(disputePda(pool.betPool))).to.equal(false);
+
+        await finalize(pool);
+
+        const betPool = await program.account.betPool.fetch(pool.betPool);
+        expect(betPool.status).to.deep.equal({ finalized: {} });
+        expect(betPool.finalStat).to.equal(230);
+        expect(betPool.amendmentCount).to.equal(1);
+        expect(betPool.amendments[0].previousStat).to.equal(180);
+        expect(betPool.amendments[0].newStat).to.equal(230);
+        expect(betPool.amendments[0].authority.toBase58()).to.equal(admin.publicKey.toBase58());
+
+        // The over side now wins the whole pot
+        const overBefore = await connection.getBalance(over.publicKey);
+        await settleClaim(pool, overPick, over);
+        expect((await connection.getBalance(over.publicKey)) - overBefore).to.equal(
+            betPool.totalOverAmount.add(betPool.totalUnderAmount).toNumber()
+        );
+        console.log("✅ Dispute upheld and corrected result paid");
+    });
+
+    it("Forfeits the bond of a rejected dispute to the insurance fund", async () => {
+        const pool = await createPool(slate, 300);
+        const disputer = await fund();
+        await placeBet(pool, await fund(), 100_000_000, true);
+        await placeBet(pool, await fund(), 100_000_000, false);
+        await lockAndPublish(pool, 320);
+
+        await openDispute(pool, disputer, 280);
+        const fundBefore = await connection.getBalance(insuranceFundPda);
+        await resolveDispute(pool, disputer, false);
+
+        expect((await connection.getBalance(insuranceFundPda)) - fundBefore).to.equal(DISPUTE_BOND);
+        const betPool = await program.account.betPool.fetch(pool.betPool);
+        expect(betPool.finalStat).to.equal(320);
+        expect(betPool.disputeOpen).to.equal(false);
+        console.log("✅ Rejected dispute forfeited", DISPUTE_BOND);
+    });
+
+    it("Lets anyone lapse a dispute the arbitrator never rules on", async () => {
+        const pool = await createPool(slate, 400);
+        const disputer = await fund();
+        await placeBet(pool, await fund(), 100_000_000, true);
+        await placeBet(pool, await fund(), 100_000_000, false);
+        await lockAndPublish(pool, 420);
+
+        await openDispute(pool, disputer, 380);
+        await expectError(expireDispute(pool, disputer), "DisputePending");
+
+        await sleep((DISPUTE_TIMEOUT + 1) * 1000);
+        const disputerBefore = await connection.getBalance(disputer.publicKey);
+        await expireDispute(pool, disputer);
+
+        // The bond goes back and the proposed result stands
+        expect((await connection.getBalance(disputer.publicKey)) - disputerBefore).to.be.at.least(DISPUTE_BOND);
+        await finalize(pool);
+        expect((await program.account.betPool.fetch(pool.betPool)).finalStat).to.equal(420);
+        console.log("✅ Lapsed dispute refunded its bond");
+    });
+
+    it("Rejects disputes of composite results", async () => {
+        const pool = await createPool(slate, 250, PASS_RUSH);
+        await setStatComponents(pool, [
+            [STAT, 100],
+            [RUSHING, 100],
+        ]);
+        await lockPool(pool);
+        await publishComposite(pool, [180, 90]);
+
+        await expectError(openDispute(pool, await fund(), 300), "CompositeDisputeUnsupported");
+        console.log("✅ Composite result can't be disputed as a single total");
+    });
+});
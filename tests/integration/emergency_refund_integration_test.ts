@@ -0,0 +1,77 @@
+import { expect } from "chai";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+    Pick,
+    Pool,
+    admin,
+    blockPda,
+    configPda,
+    connection,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    escrowPda,
+    expectError,
+    fund,
+    listPick,
+    netOf,
+    placeBet,
+    profilePda,
+    program,
+    publishResult,
+    waitUntil,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Emergency refunds", function () {
+    this.timeout(180000);
+
+    const emergencyRefund = (pool: Pool, pick: Pick, holder: PublicKey) =>
+        program.methods
+            .emergencyRefund()
+            .accountsPartial({
+                caller: admin.publicKey,
+                userPick: pick.userPick,
+                pool: pool.betPool,
+                config: configPda,
+                holder,
+                holderBlock: blockPda(holder),
+                holderProfile: profilePda(holder),
+                holderTokenAccount: getAssociatedTokenAddressSync(pick.mint, holder, true),
+                betVault: pool.betVault,
+                systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+
+    before(async () => {
+        await ensureProtocol();
+    });
+
+    it("Refunds stakes once the resolution timeout passes without a result", async () => {
+        // Kickoff soon so the pool times out within the test
+        const slate = await createSlate(15);
+        const pool = await createPool(slate, 100);
+        const over = await fund();
+        const under = await fund();
+        const overPick = await placeBet(pool, over, 100_000_000, true);
+        const underPick = await placeBet(pool, under, 60_000_000, false);
+        await listPick(pool, underPick, under);
+
+        await expectError(emergencyRefund(pool, overPick, over.publicKey), "ResolutionPending");
+
+        const betPool = await program.account.betPool.fetch(pool.betPool);
+        await waitUntil(betPool.deadline.toNumber() + betPool.resolutionTimeout.toNumber());
+
+        // Too late for the council to publish now
+        await expectError(publishResult(pool, 150), "ResolutionTimedOut");
+
+        const overBefore = await connection.getBalance(over.publicKey);
+        await emergencyRefund(pool, overPick, over.publicKey);
+        expect((await connection.getBalance(over.publicKey)) - overBefore).to.equal(netOf(100_000_000));
+        await expectError(emergencyRefund(pool, overPick, over.publicKey), "AlreadyClaimed");
+
+        // A listed pick's NFT sits in escrow; refunding there would strand the lamports
+        await expectError(emergencyRefund(pool, underPick, escrowPda(underPick.userPick)), "PickListed");
+        console.log("✅ Timed-out pool refunded", netOf(100_000_000));
+    });
+});
@@ -1,205 +1,74 @@
-import * as anchor from "@coral-xyz/anchor";
-import * as fs from "fs";
-import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
 import {
-    TOKEN_PROGRAM_ID,
-    getAccount,
-    getAssociatedTokenAddress,
-    getOrCreateAssociatedTokenAccount,
-} from "@solana/spl-token";
-
-const adminKeypair = Keypair.fromSecretKey(
-    new Uint8Array(
-        JSON.parse(fs.readFileSync(process.env.ANCHOR_WALLET!, "utf-8"))
-    )
-);
+    Pick,
+    Slate,
+    connection,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    expectError,
+    finalize,
+    fund,
+    lockAndPublish,
+    netOf,
+    placeBet,
+    program,
+    settleClaim,
+    vaultExcess,
+} from "./helpers";
 
 describe("Fantasy Sports Full Contract Test", function () {
-    this.timeout(30000);  // Increase timeout — Devnet slow sometimes!
-
-    const provider = anchor.AnchorProvider.env();
-    anchor.setProvider(provider);
-
-    const program = anchor.workspace.FantasySports;
-
-    const admin = provider.wallet;
-    const bettor = Keypair.generate();
-
-    const fixtureId = new anchor.BN(12345);
-    const sportName = "NFL";
-    const playerId = Keypair.generate().publicKey;
-    const statLine = 200;
-    const bettingDeadline = new anchor.BN(Date.now() / 1000 + 3600);
-    const betAmount = new anchor.BN(1_000_000_000);
-
-    let betPoolPda: PublicKey;
-    let userPickPda: PublicKey;
-    let feeVaultPda: PublicKey;
-    let nftMintPda: PublicKey;
-    let userAta: PublicKey;
+    this.timeout(180000);
 
-    it("Step 1️⃣ Initialize Bet Pool", async () => {
-        [betPoolPda] = await PublicKey.findProgramAddress(
-            [
-                Buffer.from("bet_pool"),
-                fixtureId.toArrayLike(Buffer, "le", 8),
-                playerId.toBuffer(),
-                Buffer.from(Uint8Array.of(...new anchor.BN(statLine).toArray("le", 4))),
-            ],
-            program.programId
-        );
+    let slate: Slate;
 
-        console.log("📍 Bet Pool PDA:", betPoolPda.toBase58());
-
-        await program.methods
-            .initializeBetPool(
-                fixtureId,
-                sportName,
-                playerId,
-                statLine,
-                bettingDeadline
-            )
-            .accounts({
-                betPool: betPoolPda,
-                admin: admin.publicKey,
-                systemProgram: SystemProgram.programId,
-            })
-            .rpc();
-
-        console.log("✅ Bet Pool initialized");
+    before("Set up config, council, sport, fixture and player", async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+        console.log("🏟️ Fixture", slate.fixtureId.toString(), "ready");
     });
 
-    it("Step 2️⃣ Place Bet (mint NFT)", async () => {
-        // Fund bettor with more SOL (5 SOL safe)
-        const transferTx = new anchor.web3.Transaction().add(
-            anchor.web3.SystemProgram.transfer({
-                fromPubkey: admin.publicKey,
-                toPubkey: bettor.publicKey,
-                lamports: 10_000_000_000,
-            })
-        );
-        await provider.sendAndConfirm(transferTx);
-        console.log("💰 Bettor funded via manual transfer:", bettor.publicKey.toBase58());
-
-        // Derive PDAs
-        [userPickPda] = await PublicKey.findProgramAddress(
-            [
-                Buffer.from("user_pick"),
-                bettor.publicKey.toBuffer(),
-                betPoolPda.toBuffer(),
-            ],
-            program.programId
-        );
-        console.log("🎟️ User Pick PDA:", userPickPda.toBase58());
-
-        [feeVaultPda] = await PublicKey.findProgramAddress(
-            [Buffer.from("fee_vault"), betPoolPda.toBuffer()],
-            program.programId
-        );
-        console.log("💰 Fee Vault PDA:", feeVaultPda.toBase58());
-
-        [nftMintPda] = await PublicKey.findProgramAddress(
-            [Buffer.from("mint"), userPickPda.toBuffer()],
-            program.programId
-        );
-        console.log("🪙 NFT Mint PDA:", nftMintPda.toBase58());
-
-        // PRE-CREATE user ATA → required for Anchor 0.31.1
-        userAta = await getAssociatedTokenAddress(nftMintPda, bettor.publicKey);
+    it("Pays out exactly the pot on an odd split", async () => {
+        const pool = await createPool(slate, 100);
 
-        await getOrCreateAssociatedTokenAccount(
-            provider.connection,
-            adminKeypair,    // payer (admin funds the creation)
-            nftMintPda,
-            bettor.publicKey
-        );
+        // Stakes chosen so that every pro-rata share truncates
+        const overStakes = [1_000_003, 2_000_001, 1_000_007];
+        const overBettors = await Promise.all(overStakes.map(() => fund()));
+        const underBettor = await fund();
 
-        console.log("🎁 User ATA:", userAta.toBase58());
-
-        // Call placeBet
-        await program.methods
-            .placeBet(betAmount, true, "https://dummy.uri/metadata.json")
-            .accounts({
-                bettor: bettor.publicKey,
-                betPool: betPoolPda,
-                userPick: userPickPda,
-                feeVault: feeVaultPda,
-                nftMint: nftMintPda,
-                userAta: userAta,
-                tokenProgram: TOKEN_PROGRAM_ID,
-                systemProgram: SystemProgram.programId,
-                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-            })
-            .signers([bettor])
-            .rpc();
-
-        console.log("✅ Bet placed + NFT minted");
-
-        // Wait a bit to ensure consistency
-        await new Promise(resolve => setTimeout(resolve, 500));
-
-        // Verify ATA now exists and holds the NFT
-        const nftAccount = await getAccount(provider.connection, userAta);
-        console.log("NFT token amount:", Number(nftAccount.amount));
-        if (Number(nftAccount.amount) !== 1) {
-            throw new Error("NFT was not minted correctly!");
+        const overPicks: Pick[] = [];
+        for (let i = 0; i < overStakes.length; i++) {
+            overPicks.push(await placeBet(pool, overBettors[i], overStakes[i], true));
         }
-    });
-
-    it("Step 3️⃣ Publish Result", async () => {
-        await program.methods
-            .publishResult({ overWins: {} })
-            .accounts({
-                betPool: betPoolPda,
-                authority: admin.publicKey,
-            })
-            .rpc();
+        const underPick = await placeBet(pool, underBettor, 1_500_000, false);
 
-        console.log("✅ Result published: OverWins");
-    });
-
-    it("Step 4️⃣ Settle Claim", async () => {
-        const bettorProvider = new anchor.AnchorProvider(
-            provider.connection,
-            new anchor.Wallet(bettor),
-            provider.opts
-        );
-
-        // Switch to bettor provider
-        anchor.setProvider(bettorProvider);
+        await lockAndPublish(pool, 150);
+        await finalize(pool);
 
-        // ⚠️ Force workspace client to use new provider!
-        (program as any).provider = bettorProvider;
+        const winningPool = overStakes.map(netOf).reduce((a, b) => a + b, 0);
+        const pot = winningPool + netOf(1_500_000);
+        const naive = overStakes.map((stake) => Math.floor((netOf(stake) * pot) / winningPool));
+        expect(naive.reduce((a, b) => a + b, 0)).to.be.lessThan(pot);
 
-        await program.methods
-            .settleClaim()
-            .accounts({
-                userPick: userPickPda,
-                betPool: betPoolPda,
-                recipient: bettor.publicKey,
-                owner: bettor.publicKey,
-            })
-            .signers([bettor])
-            .rpc();
-
-        console.log("✅ Claim settled and payout sent to bettor");
+        const payouts: number[] = [];
+        for (let i = 0; i < overPicks.length; i++) {
+            const walletBefore = await connection.getBalance(overBettors[i].publicKey);
+            await settleClaim(pool, overPicks[i], overBettors[i]);
+            payouts.push((await connection.getBalance(overBettors[i].publicKey)) - walletBefore);
+        }
+        await settleClaim(pool, underPick, underBettor);
 
-        // Switch back to admin provider
-        anchor.setProvider(provider);
-        (program as any).provider = provider;
-    });
+        // Earlier winners get their truncated share; the last takes the exact remainder
+        expect(payouts.slice(0, -1)).to.deep.equal(naive.slice(0, -1));
+        expect(payouts.reduce((a, b) => a + b, 0)).to.equal(pot);
 
-    it("Step 5️⃣ Withdraw Fees", async () => {
-        await program.methods
-            .withdrawFees()
-            .accounts({
-                admin: admin.publicKey,
-                betPool: betPoolPda,
-                feeVault: feeVaultPda,
-                recipient: admin.publicKey,
-            })
-            .rpc();
+        const betPool = await program.account.betPool.fetch(pool.betPool);
+        expect(betPool.totalPaidOut.toNumber()).to.equal(pot);
+        expect(betPool.winningStakeClaimed.toNumber()).to.equal(winningPool);
+        expect(await vaultExcess(pool.betVault)).to.equal(0);
 
-        console.log("✅ Fees withdrawn to admin");
+        // A settled pick can't be claimed a second time
+        await expectError(settleClaim(pool, overPicks[0], overBettors[0]), "AlreadyClaimed");
+        console.log("✅ Payouts", payouts, "sum to pot", pot);
     });
 });
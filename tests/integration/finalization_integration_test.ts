@@ -0,0 +1,78 @@
+import { expect } from "chai";
+import {
+    CLAIM_PERIOD,
+    DISPUTE_WINDOW,
+    chainTime,
+    connection,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    expectError,
+    finalize,
+    finalizeNow,
+    fund,
+    lockPool,
+    netOf,
+    placeBet,
+    program,
+    publishResult,
+    setFixtureStatus,
+    settleClaim,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Result finalization", function () {
+    this.timeout(180000);
+
+    before(async () => {
+        await ensureProtocol();
+    });
+
+    it("Walks a pool from open to finalized, one step at a time", async () => {
+        const slate = await createSlate();
+        const pool = await createPool(slate, 100);
+        const over = await fund();
+        const overPick = await placeBet(pool, over, 100_000_000, true);
+        await placeBet(pool, await fund(), 100_000_000, false);
+
+        await expectError(publishResult(pool, 150), "PoolNotLocked");
+        await lockPool(pool);
+        await publishResult(pool, 150);
+
+        const proposed = await program.account.betPool.fetch(pool.betPool);
+        expect(proposed.status).to.deep.equal({ proposed: {} });
+        expect(proposed.disputeWindowEnd.toNumber()).to.equal(proposed.publishedAt.toNumber() + DISPUTE_WINDOW);
+
+        // Nothing pays out while the result can still be disputed
+        await expectError(settleClaim(pool, overPick, over), "PoolNotSettled");
+        await expectError(finalizeNow(pool), "DisputeWindowOpen");
+
+        await finalize(pool);
+        const finalized = await program.account.betPool.fetch(pool.betPool);
+        expect(finalized.status).to.deep.equal({ finalized: {} });
+        expect(finalized.claimDeadline.toNumber()).to.be.at.most((await chainTime()) + CLAIM_PERIOD);
+
+        await expectError(finalizeNow(pool), "AlreadyPublished");
+        await expectError(publishResult(pool, 160), "AlreadyPublished");
+        await settleClaim(pool, overPick, over);
+        console.log("✅ Pool finalized with claims open until", finalized.claimDeadline.toNumber());
+    });
+
+    it("Refuses to finalize once the fixture is postponed", async () => {
+        const slate = await createSlate();
+        const pool = await createPool(slate, 200);
+        const over = await fund();
+        const overPick = await placeBet(pool, over, 100_000_000, true);
+        await placeBet(pool, await fund(), 100_000_000, false);
+        await lockPool(pool);
+        await publishResult(pool, 250);
+
+        await setFixtureStatus(slate, "postponed");
+        await expectError(finalize(pool), "FixturePostponed");
+
+        // The pool settles as a refund instead, never as a win
+        const overBefore = await connection.getBalance(over.publicKey);
+        await settleClaim(pool, overPick, over);
+        expect((await connection.getBalance(over.publicKey)) - overBefore).to.equal(netOf(100_000_000));
+        console.log("✅ Proposed pool on a postponed fixture refunded");
+    });
+});
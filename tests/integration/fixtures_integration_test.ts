@@ -0,0 +1,74 @@
+import { expect } from "chai";
+import {
+    connection,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    expectError,
+    fund,
+    lockPool,
+    netOf,
+    placeBet,
+    program,
+    publishResult,
+    setFixtureStatus,
+    settleClaim,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Fixtures", function () {
+    this.timeout(180000);
+
+    before(async () => {
+        await ensureProtocol();
+    });
+
+    it("Groups pools under a fixture and closes betting once it starts", async () => {
+        const slate = await createSlate();
+        const first = await createPool(slate, 100);
+        const second = await createPool(slate, 200);
+
+        const fixture = await program.account.fixture.fetch(slate.fixture);
+        for (const pool of [first, second]) {
+            const betPool = await program.account.betPool.fetch(pool.betPool);
+            expect(betPool.fixture.toBase58()).to.equal(slate.fixture.toBase58());
+            expect(betPool.fixtureId.toString()).to.equal(slate.fixtureId.toString());
+            // Pools take the fixture's kickoff as their betting deadline
+            expect(betPool.deadline.toNumber()).to.equal(fixture.scheduledStart.toNumber());
+        }
+
+        await placeBet(first, await fund(), 50_000_000, true);
+        await setFixtureStatus(slate, "live");
+        await expectError(placeBet(second, await fund(), 50_000_000, true), "FixtureNotScheduled");
+
+        await setFixtureStatus(slate, "final");
+        expect((await program.account.fixture.fetch(slate.fixture)).status).to.deep.equal({ final: {} });
+        console.log("✅ Fixture", slate.fixtureId.toString(), "went live and final");
+    });
+
+    it("Voids every pool on the fixture when it is postponed", async () => {
+        const slate = await createSlate();
+        const pool = await createPool(slate, 300);
+        const over = await fund();
+        const under = await fund();
+        const overPick = await placeBet(pool, over, 100_000_000, true);
+        const underPick = await placeBet(pool, under, 60_000_000, false);
+        await lockPool(pool);
+
+        await setFixtureStatus(slate, "postponed");
+
+        // Postponed is terminal: no restart, no results, no new pools
+        await expectError(setFixtureStatus(slate, "live"), "InvalidFixtureStatus");
+        await expectError(publishResult(pool, 350), "FixturePostponed");
+        await expectError(createPool(slate, 310), "FixtureNotScheduled");
+
+        // Both sides get their stake back, whichever way the game would have gone
+        const overBefore = await connection.getBalance(over.publicKey);
+        await settleClaim(pool, overPick, over);
+        expect((await connection.getBalance(over.publicKey)) - overBefore).to.equal(netOf(100_000_000));
+
+        const underBefore = await connection.getBalance(under.publicKey);
+        await settleClaim(pool, underPick, under);
+        expect((await connection.getBalance(under.publicKey)) - underBefore).to.equal(netOf(60_000_000));
+        console.log("✅ Postponed fixture refunded both sides");
+    });
+});
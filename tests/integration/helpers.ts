@@ -0,0 +1,674 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+    AccountMeta,
+    Keypair,
+    PublicKey,
+    SystemProgram,
+    SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
+import {
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+    TOKEN_PROGRAM_ID,
+    getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+
+export const BN = anchor.BN;
+
+// Keep these short: every finalize waits out both windows
+export const DISPUTE_WINDOW = 6;
+export const CORRECTION_WINDOW = 6;
+export const DISPUTE_TIMEOUT = 4;
+export const CLAIM_PERIOD = 3600;
+// Pools inherit this at creation; fixtures an hour out never get near it
+export const RESOLUTION_TIMEOUT = 5;
+export const DISPUTE_BOND = 10_000_000;
+export const CRANK_TIP = 1_000;
+
+export const SESSION_PLACE_BET = 1 << 0;
+export const SESSION_SETTLE_CLAIM = 1 << 1;
+export const PAUSE_BETTING = 1 << 0;
+export const PAUSE_MARKETPLACE = 1 << 1;
+export const PAUSE_CLAIMS = 1 << 2;
+
+export const SPORT = "NFL";
+export const STAT = "PASSING_YARDS";
+export const RUSHING = "RUSHING_YARDS";
+export const PASS_RUSH = "PASS_RUSH_YARDS";
+
+export const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+
+export const program = anchor.workspace.FantasySports;
+export const connection = provider.connection;
+
+// The program hardcodes ADMIN_PUBKEY; run with that keypair as ANCHOR_WALLET
+export const admin = provider.wallet;
+
+export type Slate = {
+    fixtureId: anchor.BN;
+    fixture: PublicKey;
+    player: PublicKey;
+};
+
+export type Pool = Slate & {
+    statName: string;
+    statLine: number;
+    betPool: PublicKey;
+    feeVault: PublicKey;
+    betVault: PublicKey;
+};
+
+export type Pick = {
+    bettor: PublicKey;
+    userPick: PublicKey;
+    mint: PublicKey;
+    tokenAccount: PublicKey;
+};
+
+export type BetOptions = {
+    authority?: Keypair;
+    payer?: Keypair;
+    sessionToken?: PublicKey;
+    balance?: PublicKey;
+    attestation?: PublicKey;
+};
+
+export type ClaimOptions = {
+    authority?: Keypair;
+    sessionToken?: PublicKey;
+    balance?: PublicKey;
+    profile?: PublicKey;
+};
+
+// Zero-padded fixed-size name, as the program stores them
+export const name = (value: string, len = 32): number[] => {
+    const bytes = Array.from(Buffer.from(value));
+    return [...bytes, ...new Array(len - bytes.length).fill(0)];
+};
+
+export const u64 = (value: anchor.BN | number): Buffer => new BN(value).toArrayLike(Buffer, "le", 8);
+export const u32 = (value: number): Buffer => new BN(value).toArrayLike(Buffer, "le", 4);
+export const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+export const netOf = (amount: number) => amount - Math.floor((amount * 5) / 100);
+
+export const pda = (...seeds: (Buffer | Uint8Array)[]): PublicKey =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+export const configPda = pda(Buffer.from("config"));
+export const councilPda = pda(Buffer.from("council"));
+export const insuranceFundPda = pda(Buffer.from("insurance_fund"));
+export const jackpotVaultPda = pda(Buffer.from("jackpot_vault"));
+export const sportPda = pda(Buffer.from("sport"), Buffer.from(name(SPORT)));
+export const profilePda = (wallet: PublicKey) => pda(Buffer.from("user_profile"), wallet.toBuffer());
+export const blockPda = (wallet: PublicKey) => pda(Buffer.from("blocked"), wallet.toBuffer());
+export const balancePda = (wallet: PublicKey) => pda(Buffer.from("balance"), wallet.toBuffer());
+export const escrowPda = (userPick: PublicKey) => pda(Buffer.from("escrow"), userPick.toBuffer());
+export const attestationPda = (wallet: PublicKey) => pda(Buffer.from("attestation"), wallet.toBuffer());
+export const disputePda = (betPool: PublicKey) => pda(Buffer.from("dispute"), betPool.toBuffer());
+export const roleGrantPda = (role: number, grantee: PublicKey) =>
+    pda(Buffer.from("role"), Buffer.from([role]), grantee.toBuffer());
+
+// Fixture and player ids must not collide across suites sharing one validator
+let nextId = Date.now() * 1000;
+const uniqueId = () => new BN(nextId++);
+
+export const exists = async (address: PublicKey) => (await connection.getAccountInfo(address)) !== null;
+
+export const fund = async (lamports = 2_000_000_000): Promise<Keypair> => {
+    const wallet = Keypair.generate();
+    const tx = new anchor.web3.Transaction().add(
+        SystemProgram.transfer({
+            fromPubkey: admin.publicKey,
+            toPubkey: wallet.publicKey,
+            lamports,
+        })
+    );
+    await provider.sendAndConfirm(tx);
+    return wallet;
+};
+
+export const chainTime = async (): Promise<number> => {
+    const slot = await connection.getSlot();
+    return (await connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+};
+
+export const waitUntil = async (timestamp: number) => {
+    while ((await chainTime()) <= timestamp) {
+        await sleep(1000);
+    }
+};
+
+// Lamports above the rent-exempt minimum, i.e. what the program may still pay out
+export const vaultExcess = async (vault: PublicKey): Promise<number> => {
+    const info = await connection.getAccountInfo(vault);
+    const rentFloor = await connection.getMinimumBalanceForRentExemption(info!.data.length);
+    return info!.lamports - rentFloor;
+};
+
+export const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+        await promise;
+    } catch (err: any) {
+        const actual = err?.error?.errorCode?.code ?? String(err);
+        expect(actual).to.include(code);
+        return;
+    }
+    expect.fail(`expected ${code}`);
+};
+
+export const configParams = (overrides: object = {}) => ({
+    treasury: admin.publicKey,
+    claimPeriod: new BN(CLAIM_PERIOD),
+    unclaimedDestination: { treasury: {} },
+    resolutionTimeout: new BN(RESOLUTION_TIMEOUT),
+    disputeWindow: new BN(DISPUTE_WINDOW),
+    disputeTimeout: new BN(DISPUTE_TIMEOUT),
+    arbitrator: admin.publicKey,
+    disputeBond: new BN(DISPUTE_BOND),
+    disputeReward: new BN(0),
+    correctionWindow: new BN(CORRECTION_WINDOW),
+    crankTip: new BN(CRANK_TIP),
+    guardian: admin.publicKey,
+    ...overrides,
+});
+
+export const executeCouncilAction = async (action: object, accounts: object = {}) => {
+    const council = await program.account.council.fetch(councilPda);
+    const proposal = pda(Buffer.from("proposal"), councilPda.toBuffer(), u64(council.proposalCount));
+
+    await program.methods
+        .proposeAction(action)
+        .accountsPartial({
+            proposer: admin.publicKey,
+            council: councilPda,
+            proposal,
+            systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    await program.methods
+        .executeAction()
+        .accountsPartial({
+            council: councilPda,
+            proposal,
+            config: configPda,
+            betPool: null,
+            fixture: null,
+            sport: null,
+            feeVault: null,
+            treasury: null,
+            jackpotVault: null,
+            recipient: null,
+            ...accounts,
+        })
+        .rpc();
+};
+
+// Suites that change config put the defaults back when they are done
+export const updateConfig = (overrides: object = {}) =>
+    executeCouncilAction({ updateConfig: { "0": configParams(overrides) } });
+
+export const upsertStatType = (statName: string, maxValue: number) =>
+    program.methods
+        .upsertStatType({
+            statName: name(statName),
+            unit: name("YDS", 16),
+            decimals: 0,
+            minValue: 0,
+            maxValue,
+        })
+        .accountsPartial({ sport: sportPda, authority: admin.publicKey })
+        .rpc();
+
+// Idempotent: every suite calls this, and only the first run on a validator creates anything
+export const ensureProtocol = async () => {
+    if (!(await exists(configPda))) {
+        await program.methods
+            .initializeConfig(configParams())
+            .accountsPartial({
+                admin: admin.publicKey,
+                config: configPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+    }
+
+    if (!(await exists(insuranceFundPda))) {
+        await program.methods
+            .initializeInsuranceFund()
+            .accountsPartial({
+                admin: admin.publicKey,
+                insuranceFund: insuranceFundPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+    }
+
+    if (!(await exists(jackpotVaultPda))) {
+        await program.methods
+            .initializeJackpotVault()
+            .accountsPartial({
+                admin: admin.publicKey,
+                jackpotVault: jackpotVaultPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+    }
+
+    // A single-member council keeps proposals executable straight away
+    if (!(await exists(councilPda))) {
+        await program.methods
+            .initializeCouncil([admin.publicKey], 1)
+            .accountsPartial({
+                admin: admin.publicKey,
+                council: councilPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+    }
+
+    if (!(await exists(sportPda))) {
+        await program.methods
+            .initializeSport(name(SPORT))
+            .accountsPartial({
+                admin: admin.publicKey,
+                sport: sportPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+    }
+
+    await upsertStatType(STAT, 1000);
+    await upsertStatType(RUSHING, 1000);
+    await upsertStatType(PASS_RUSH, 2000);
+};
+
+export const createPlayer = async (): Promise<PublicKey> => {
+    const externalId = uniqueId();
+    const player = pda(Buffer.from("player"), Buffer.from(name(SPORT)), u64(externalId));
+
+    await program.methods
+        .registerPlayer(name(SPORT), externalId, name("QB_ONE"), name("HOME"))
+        .accountsPartial({
+            admin: admin.publicKey,
+            roleGrant: null,
+            sport: sportPda,
+            player,
+            systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    return player;
+};
+
+// A fresh fixture and player; startsIn is the betting deadline in seconds from now
+export const createSlate = async (startsIn = 3600): Promise<Slate> => {
+    const fixtureId = uniqueId();
+    const fixture = pda(Buffer.from("fixture"), u64(fixtureId));
+
+    await program.methods
+        .initializeFixture(
+            fixtureId,
+            name(SPORT),
+            name("HOME"),
+            name("AWAY"),
+            new BN((await chainTime()) + startsIn)
+        )
+        .accountsPartial({
+            admin: admin.publicKey,
+            roleGrant: null,
+            sport: sportPda,
+            fixture,
+            systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    return { fixtureId, fixture, player: await createPlayer() };
+};
+
+export const poolPda = (slate: Slate, statLine: number, statName = STAT) =>
+    pda(
+        Buffer.from("bet_pool"),
+        u64(slate.fixtureId),
+        Buffer.from(name(SPORT)),
+        slate.player.toBuffer(),
+        Buffer.from(name(statName)),
+        u32(statLine)
+    );
+
+export const createPool = async (
+    slate: Slate,
+    statLine: number,
+    statName = STAT,
+    signer: { authority: Keypair; roleGrant: PublicKey | null } | null = null
+): Promise<Pool> => {
+    const betPool = poolPda(slate, statLine, statName);
+    const feeVault = pda(Buffer.from("fee_vault"), betPool.toBuffer());
+    const betVault = pda(Buffer.from("bet_vault"), betPool.toBuffer());
+
+    const builder = program.methods
+        .initializeBetPool(slate.fixtureId, slate.player, name(statName), statLine, name(SPORT))
+        .accountsPartial({
+            admin: signer?.authority.publicKey ?? admin.publicKey,
+            roleGrant: signer?.roleGrant ?? null,
+            fixture: slate.fixture,
+            player: slate.player,
+            sport: sportPda,
+            config: configPda,
+            betPool,
+            feeVault,
+            betVault,
+            systemProgram: SystemProgram.programId,
+        });
+    await (signer ? builder.signers([signer.authority]) : builder).rpc();
+
+    return { ...slate, statName, statLine, betPool, feeVault, betVault };
+};
+
+export const placeBet = async (
+    pool: Pool,
+    bettor: Keypair | PublicKey,
+    amount: number,
+    pickSide: boolean,
+    options: BetOptions = {}
+): Promise<Pick> => {
+    const bettorKey = bettor instanceof Keypair ? bettor.publicKey : bettor;
+    const authority = options.authority ?? (bettor as Keypair);
+    const payer = options.payer ?? authority;
+
+    const userNonce = pda(Buffer.from("user_nonce"), bettorKey.toBuffer(), pool.betPool.toBuffer());
+    const nonce = (await exists(userNonce))
+        ? (await program.account.userNonce.fetch(userNonce)).count
+        : new BN(0);
+    const userPick = pda(Buffer.from("user_pick"), bettorKey.toBuffer(), pool.betPool.toBuffer(), u64(nonce));
+    // The mint doubles as its own mint authority
+    const mint = pda(Buffer.from("mint"), userPick.toBuffer());
+    const tokenAccount = getAssociatedTokenAddressSync(mint, bettorKey);
+
+    const signers = payer === authority ? [authority] : [authority, payer];
+    await program.methods
+        .placeBet(
+            pool.fixtureId,
+            pool.player,
+            name(pool.statName),
+            pool.statLine,
+            new BN(amount),
+            pickSide,
+            name(SPORT)
+        )
+        .accountsPartial({
+            bettor: bettorKey,
+            authority: authority.publicKey,
+            payer: payer.publicKey,
+            sessionToken: options.sessionToken ?? null,
+            betPool: pool.betPool,
+            fixture: pool.fixture,
+            sport: sportPda,
+            config: configPda,
+            player: pool.player,
+            userNonce,
+            userProfile: profilePda(bettorKey),
+            attestation: options.attestation ?? null,
+            bettorBlock: blockPda(bettorKey),
+            bettorBalance: options.balance ?? null,
+            userPick,
+            mint,
+            mintAuthority: mint,
+            userTokenAccount: tokenAccount,
+            feeVault: pool.feeVault,
+            betVault: pool.betVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers(signers)
+        .rpc();
+
+    return { bettor: bettorKey, userPick, mint, tokenAccount };
+};
+
+export const settleClaim = async (pool: Pool, pick: Pick, holder: Keypair | PublicKey, options: ClaimOptions = {}) => {
+    const holderKey = holder instanceof Keypair ? holder.publicKey : holder;
+    const authority = options.authority ?? (holder as Keypair);
+
+    await program.methods
+        .settleClaim()
+        .accountsPartial({
+            userPick: pick.userPick,
+            betPool: pool.betPool,
+            fixture: pool.fixture,
+            sport: sportPda,
+            config: configPda,
+            recipient: holderKey,
+            authority: authority.publicKey,
+            sessionToken: options.sessionToken ?? null,
+            recipientProfile: options.profile ?? profilePda(holderKey),
+            recipientBlock: blockPda(holderKey),
+            recipientBalance: options.balance ?? null,
+            recipientTokenAccount: getAssociatedTokenAddressSync(pick.mint, holderKey),
+            betVault: pool.betVault,
+            systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+};
+
+export const lockPool = (pool: Pool) =>
+    program.methods
+        .lockBetPool()
+        .accountsPartial({ betPool: pool.betPool, authority: admin.publicKey, roleGrant: null })
+        .rpc();
+
+export const publishResult = (pool: Pool, finalStat: number) =>
+    executeCouncilAction(
+        { publishResult: { betPool: pool.betPool, finalStat } },
+        { betPool: pool.betPool, fixture: pool.fixture, sport: sportPda }
+    );
+
+export const lockAndPublish = async (pool: Pool, finalStat: number) => {
+    await lockPool(pool);
+    await publishResult(pool, finalStat);
+};
+
+export const finalizeNow = (pool: Pool) =>
+    program.methods
+        .finalizeResult()
+        .accountsPartial({ betPool: pool.betPool, fixture: pool.fixture, config: configPda })
+        .rpc();
+
+// Finalization waits for both the dispute and the correction window to close
+export const finalize = async (pool: Pool) => {
+    const betPool = await program.account.betPool.fetch(pool.betPool);
+    await waitUntil(
+        Math.max(
+            betPool.disputeWindowEnd.toNumber(),
+            betPool.publishedAt.toNumber() + betPool.correctionWindow.toNumber()
+        )
+    );
+    await finalizeNow(pool);
+};
+
+// The five accounts crank_settle reads per pick
+export const crankAccounts = (pick: Pick, holder = pick.bettor): AccountMeta[] => [
+    { pubkey: pick.userPick, isWritable: true, isSigner: false },
+    { pubkey: getAssociatedTokenAddressSync(pick.mint, holder, true), isWritable: false, isSigner: false },
+    { pubkey: holder, isWritable: true, isSigner: false },
+    { pubkey: blockPda(holder), isWritable: true, isSigner: false },
+    { pubkey: profilePda(holder), isWritable: true, isSigner: false },
+];
+
+export const crankSettle = (pool: Pool, remainingAccounts: AccountMeta[]) =>
+    program.methods
+        .crankSettle()
+        .accountsPartial({
+            cranker: admin.publicKey,
+            betPool: pool.betPool,
+            fixture: pool.fixture,
+            sport: sportPda,
+            config: configPda,
+            feeVault: pool.feeVault,
+            betVault: pool.betVault,
+            systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(remainingAccounts)
+        .rpc();
+
+export const deposit = (owner: Keypair, amount: number) =>
+    program.methods
+        .deposit(new BN(amount))
+        .accountsPartial({
+            owner: owner.publicKey,
+            ownerBlock: blockPda(owner.publicKey),
+            balance: balancePda(owner.publicKey),
+            systemProgram: SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+
+export const blockWallet = (wallet: PublicKey, reasonCode = 1) =>
+    program.methods
+        .blockWallet(wallet, reasonCode)
+        .accountsPartial({
+            authority: admin.publicKey,
+            roleGrant: null,
+            blockedWallet: blockPda(wallet),
+            systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+export const listPick = (pool: Pool, pick: Pick, seller: Keypair, mint = pick.mint) =>
+    program.methods
+        .listPickNft()
+        .accountsPartial({
+            seller: seller.publicKey,
+            userPick: pick.userPick,
+            mint,
+            pool: pool.betPool,
+            config: configPda,
+            sport: sportPda,
+            sellerBlock: blockPda(seller.publicKey),
+            sellerTokenAccount: getAssociatedTokenAddressSync(mint, seller.publicKey),
+            escrowTokenAccount: getAssociatedTokenAddressSync(mint, escrowPda(pick.userPick), true),
+            escrowPda: escrowPda(pick.userPick),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([seller])
+        .rpc();
+
+export const buyPick = (
+    pool: Pool,
+    pick: Pick,
+    seller: PublicKey,
+    buyer: Keypair,
+    salePrice: number,
+    balances: { buyer?: PublicKey; seller?: PublicKey; attestation?: PublicKey } = {}
+) =>
+    program.methods
+        .buyPickNft(new BN(salePrice))
+        .accountsPartial({
+            seller,
+            buyer: buyer.publicKey,
+            userPick: pick.userPick,
+            mint: pick.mint,
+            pool: pool.betPool,
+            escrowTokenAccount: getAssociatedTokenAddressSync(pick.mint, escrowPda(pick.userPick), true),
+            escrowPda: escrowPda(pick.userPick),
+            buyerTokenAccount: getAssociatedTokenAddressSync(pick.mint, buyer.publicKey),
+            buyerProfile: profilePda(buyer.publicKey),
+            sellerProfile: profilePda(seller),
+            attestation: balances.attestation ?? null,
+            buyerBlock: blockPda(buyer.publicKey),
+            sellerBlock: blockPda(seller),
+            buyerBalance: balances.buyer ?? null,
+            sellerBalance: balances.seller ?? null,
+            royaltyVault: pool.feeVault,
+            config: configPda,
+            sport: sportPda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([buyer])
+        .rpc();
+
+export const setStatComponents = (pool: Pool, components: [string, number][]) =>
+    program.methods
+        .setStatComponents(components.map(([statName, weight]) => ({ statName: name(statName), weight })))
+        .accountsPartial({
+            betPool: pool.betPool,
+            sport: sportPda,
+            authority: admin.publicKey,
+            roleGrant: null,
+        })
+        .rpc();
+
+// Component values are padded to the fixed-size array the action carries
+export const publishComposite = (pool: Pool, values: number[]) =>
+    executeCouncilAction(
+        {
+            publishCompositeResult: {
+                betPool: pool.betPool,
+                componentValues: [...values, ...new Array(4 - values.length).fill(0)],
+            },
+        },
+        { betPool: pool.betPool, fixture: pool.fixture, sport: sportPda }
+    );
+
+export const setFixtureStatus = (slate: Slate, status: string) =>
+    program.methods
+        .updateFixtureStatus({ [status]: {} })
+        .accountsPartial({ fixture: slate.fixture, authority: admin.publicKey, roleGrant: null })
+        .rpc();
+
+export const withdrawFees = (pool: Pool, amount: number) =>
+    executeCouncilAction(
+        { withdrawFees: { betPool: pool.betPool, amount: new BN(amount) } },
+        { betPool: pool.betPool, feeVault: pool.feeVault, treasury: admin.publicKey }
+    );
+
+export const closePool = (pool: Pool) =>
+    program.methods
+        .closeBetPool()
+        .accountsPartial({
+            admin: admin.publicKey,
+            roleGrant: null,
+            betPool: pool.betPool,
+            fixture: pool.fixture,
+            config: configPda,
+            treasury: admin.publicKey,
+            feeVault: pool.feeVault,
+            betVault: pool.betVault,
+            systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+// Role discriminants, as used in the role grant seeds
+export const ROLES = {
+    poolCreator: 0,
+    resolver: 1,
+    treasurer: 2,
+    pauser: 3,
+    marketplaceAdmin: 4,
+    compliance: 5,
+} as const;
+
+export const grantRole = async (role: keyof typeof ROLES, grantee: PublicKey): Promise<PublicKey> => {
+    const roleGrant = roleGrantPda(ROLES[role], grantee);
+    await program.methods
+        .grantRole({ [role]: {} }, grantee)
+        .accountsPartial({
+            admin: admin.publicKey,
+            roleGrant,
+            systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    return roleGrant;
+};
+
+export const revokeRole = (roleGrant: PublicKey) =>
+    program.methods.revokeRole().accountsPartial({ admin: admin.publicKey, roleGrant }).rpc();
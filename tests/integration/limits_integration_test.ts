@@ -0,0 +1,88 @@
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+    BN,
+    Slate,
+    chainTime,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    expectError,
+    fund,
+    placeBet,
+    profilePda,
+    program,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Responsible-gaming limits", function () {
+    this.timeout(180000);
+
+    const LIMIT_INCREASE_COOLDOWN = 24 * 60 * 60;
+
+    let slate: Slate;
+
+    const setLimits = (wallet: Keypair, dailyDepositLimit: number, weeklyDepositLimit = 0, weeklyLossLimit = 0) =>
+        program.methods
+            .setLimits({
+                dailyDepositLimit: new BN(dailyDepositLimit),
+                weeklyDepositLimit: new BN(weeklyDepositLimit),
+                weeklyLossLimit: new BN(weeklyLossLimit),
+            })
+            .accountsPartial({ wallet: wallet.publicKey, userProfile: profilePda(wallet.publicKey) })
+            .signers([wallet])
+            .rpc();
+
+    const selfExclude = (wallet: Keypair, duration: number) =>
+        program.methods
+            .selfExclude(new BN(duration))
+            .accountsPartial({ wallet: wallet.publicKey, userProfile: profilePda(wallet.publicKey) })
+            .signers([wallet])
+            .rpc();
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+    });
+
+    it("Applies tighter limits at once and defers looser ones", async () => {
+        const pool = await createPool(slate, 100);
+        const bettor = await fund();
+
+        await setLimits(bettor, 100_000_000);
+        await placeBet(pool, bettor, 60_000_000, true);
+        await expectError(placeBet(pool, bettor, 50_000_000, true), "DailyDepositLimitReached");
+
+        // Raising the limit only takes effect after the cool-down
+        await setLimits(bettor, 500_000_000);
+        const profile = await program.account.userProfile.fetch(profilePda(bettor.publicKey));
+        expect(profile.limits.dailyDepositLimit.toNumber()).to.equal(100_000_000);
+        expect(profile.pendingLimits.dailyDepositLimit.toNumber()).to.equal(500_000_000);
+        expect(profile.pendingLimitsAt.toNumber()).to.be.at.least((await chainTime()) + LIMIT_INCREASE_COOLDOWN - 60);
+        expect(profile.dayDeposited.toNumber()).to.equal(60_000_000);
+        await expectError(placeBet(pool, bettor, 50_000_000, true), "DailyDepositLimitReached");
+
+        // Tightening again cancels the pending increase
+        await setLimits(bettor, 80_000_000);
+        const tightened = await program.account.userProfile.fetch(profilePda(bettor.publicKey));
+        expect(tightened.limits.dailyDepositLimit.toNumber()).to.equal(80_000_000);
+        expect(tightened.pendingLimitsAt.toNumber()).to.equal(0);
+        await placeBet(pool, bettor, 20_000_000, false);
+        console.log("✅ Daily limit held at the tighter value");
+    });
+
+    it("Blocks betting during self-exclusion, which can't be shortened", async () => {
+        const pool = await createPool(slate, 200);
+        const bettor = await fund();
+        await placeBet(pool, bettor, 10_000_000, true);
+
+        await expectError(selfExclude(bettor, 0), "InvalidExclusionPeriod");
+        await selfExclude(bettor, 3600);
+        await expectError(placeBet(pool, bettor, 10_000_000, true), "SelfExcluded");
+
+        const until = (await program.account.userProfile.fetch(profilePda(bettor.publicKey))).selfExcludedUntil;
+        await selfExclude(bettor, 60);
+        const after = (await program.account.userProfile.fetch(profilePda(bettor.publicKey))).selfExcludedUntil;
+        expect(after.toNumber()).to.equal(until.toNumber());
+        console.log("✅ Self-excluded until", until.toNumber());
+    });
+});
@@ -0,0 +1,98 @@
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+    PAUSE_BETTING,
+    PAUSE_CLAIMS,
+    PAUSE_MARKETPLACE,
+    Pool,
+    Slate,
+    admin,
+    configPda,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    expectError,
+    finalize,
+    fund,
+    listPick,
+    lockAndPublish,
+    placeBet,
+    program,
+    settleClaim,
+    sportPda,
+    updateConfig,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Circuit breaker", function () {
+    this.timeout(180000);
+
+    let slate: Slate;
+    const guardian = Keypair.generate();
+
+    const setPause = (scope: "global" | "sport" | "pool", flags: number, authority?: Keypair, pool?: Pool) => {
+        const builder = program.methods
+            .setPause({ [scope]: {} }, flags, 1)
+            .accountsPartial({
+                authority: authority?.publicKey ?? admin.publicKey,
+                roleGrant: null,
+                config: configPda,
+                sport: scope === "sport" ? sportPda : null,
+                betPool: pool?.betPool ?? null,
+            });
+        return (authority ? builder.signers([authority]) : builder).rpc();
+    };
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+        await updateConfig({ guardian: guardian.publicKey });
+    });
+
+    after(async () => {
+        await updateConfig();
+    });
+
+    it("Lets the guardian pause a pool but only a pauser resume it", async () => {
+        const pool = await createPool(slate, 100);
+        await setPause("pool", PAUSE_BETTING, guardian, pool);
+        expect((await program.account.betPool.fetch(pool.betPool)).pausedFlags).to.equal(PAUSE_BETTING);
+        await expectError(placeBet(pool, await fund(), 50_000_000, true), "Paused");
+
+        await expectError(setPause("pool", 0, guardian, pool), "MissingRole");
+        await setPause("pool", 0, undefined, pool);
+        await placeBet(pool, await fund(), 50_000_000, true);
+        console.log("✅ Guardian paused, admin resumed");
+    });
+
+    it("Pauses claims globally and the marketplace per sport", async () => {
+        const pool = await createPool(slate, 200);
+        const over = await fund();
+        const under = await fund();
+        const overPick = await placeBet(pool, over, 100_000_000, true);
+        const underPick = await placeBet(pool, under, 100_000_000, false);
+
+        await setPause("sport", PAUSE_MARKETPLACE, guardian);
+        await expectError(listPick(pool, underPick, under), "Paused");
+        await setPause("sport", 0);
+
+        await lockAndPublish(pool, 250);
+        await finalize(pool);
+
+        await setPause("global", PAUSE_CLAIMS);
+        try {
+            await expectError(settleClaim(pool, overPick, over), "Paused");
+        } finally {
+            await setPause("global", 0);
+        }
+        await settleClaim(pool, overPick, over);
+        console.log("✅ Claims resumed after the global pause");
+    });
+
+    it("Rejects pauses from anyone else and unknown flags", async () => {
+        const pool = await createPool(slate, 300);
+        await expectError(setPause("pool", PAUSE_BETTING, await fund(10_000_000), pool), "MissingRole");
+        await expectError(setPause("pool", 1 << 3, guardian, pool), "InvalidPauseFlags");
+        await expectError(setPause("pool", PAUSE_BETTING, guardian), "PauseTargetMissing");
+        console.log("✅ Unauthorized and malformed pauses rejected");
+    });
+});
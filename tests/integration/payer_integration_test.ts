@@ -0,0 +1,68 @@
+import { expect } from "chai";
+import { PublicKey } from "@solana/web3.js";
+import {
+    Pick,
+    Slate,
+    connection,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    exists,
+    expectError,
+    finalize,
+    fund,
+    lockAndPublish,
+    placeBet,
+    program,
+    settleClaim,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Rent payer", function () {
+    this.timeout(180000);
+
+    let slate: Slate;
+
+    const closeUserPick = (pick: Pick, rentPayer: PublicKey) =>
+        program.methods.closeUserPick().accountsPartial({ userPick: pick.userPick, rentPayer }).rpc();
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+    });
+
+    it("Lets a relayer front the rent and reclaim it after settlement", async () => {
+        const pool = await createPool(slate, 650);
+        const bettor = await fund();
+        const opponent = await fund();
+        const relayer = await fund();
+        const stake = 100_000_000;
+
+        const bettorBefore = await connection.getBalance(bettor.publicKey);
+        const relayerBefore = await connection.getBalance(relayer.publicKey);
+        const pick = await placeBet(pool, bettor, stake, true, { payer: relayer });
+        await placeBet(pool, opponent, stake, false);
+
+        // The bettor parts with the stake only; every account's rent is on the relayer
+        expect(bettorBefore - (await connection.getBalance(bettor.publicKey))).to.equal(stake);
+        expect(await connection.getBalance(relayer.publicKey)).to.be.lessThan(relayerBefore);
+        const userPick = await program.account.userPick.fetch(pick.userPick);
+        expect(userPick.rentPayer.toBase58()).to.equal(relayer.publicKey.toBase58());
+
+        // Nothing can be reclaimed while the pick is still live
+        await expectError(closeUserPick(pick, relayer.publicKey), "PoolNotSettled");
+
+        await lockAndPublish(pool, 700);
+        await finalize(pool);
+        await settleClaim(pool, pick, bettor);
+
+        // Only the recorded payer may receive the rent
+        await expectError(closeUserPick(pick, bettor.publicKey), "Unauthorized");
+
+        const rent = await connection.getBalance(pick.userPick);
+        const relayerBeforeClose = await connection.getBalance(relayer.publicKey);
+        await closeUserPick(pick, relayer.publicKey);
+        expect(await exists(pick.userPick)).to.equal(false);
+        expect((await connection.getBalance(relayer.publicKey)) - relayerBeforeClose).to.equal(rent);
+        console.log("✅ Relayer recovered", rent, "lamports of pick rent");
+    });
+});
@@ -0,0 +1,60 @@
+import { expect } from "chai";
+import { PublicKey } from "@solana/web3.js";
+import {
+    SPORT,
+    admin,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    expectError,
+    fund,
+    name,
+    placeBet,
+    program,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Players", function () {
+    this.timeout(180000);
+
+    const updatePlayer = (player: PublicKey, team: string, active: boolean) =>
+        program.methods
+            .updatePlayer(name("QB_ONE"), name(team), active)
+            .accountsPartial({ player, authority: admin.publicKey, roleGrant: null })
+            .rpc();
+
+    before(async () => {
+        await ensureProtocol();
+    });
+
+    it("Binds pools to a registered player", async () => {
+        const slate = await createSlate();
+        const registered = await program.account.player.fetch(slate.player);
+        expect(registered.sportName).to.deep.equal(name(SPORT));
+        expect(registered.active).to.equal(true);
+
+        const pool = await createPool(slate, 100);
+        expect((await program.account.betPool.fetch(pool.betPool)).playerId.toBase58()).to.equal(
+            slate.player.toBase58()
+        );
+
+        // A trade updates the player without touching its pools
+        await updatePlayer(slate.player, "AWAY", true);
+        expect((await program.account.player.fetch(slate.player)).team).to.deep.equal(name("AWAY"));
+        await placeBet(pool, await fund(), 50_000_000, true);
+        console.log("✅ Pool bound to player", slate.player.toBase58());
+    });
+
+    it("Rejects pools and bets for an inactive player", async () => {
+        const slate = await createSlate();
+        const pool = await createPool(slate, 100);
+
+        await updatePlayer(slate.player, "HOME", false);
+        await expectError(createPool(slate, 200), "PlayerInactive");
+        await expectError(placeBet(pool, await fund(), 50_000_000, true), "PlayerInactive");
+
+        // Reactivating reopens the existing pool
+        await updatePlayer(slate.player, "HOME", true);
+        await placeBet(pool, await fund(), 50_000_000, true);
+        console.log("✅ Inactive player rejected, then reinstated");
+    });
+});
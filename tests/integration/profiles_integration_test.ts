@@ -0,0 +1,83 @@
+import { expect } from "chai";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import {
+    Slate,
+    admin,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    expectError,
+    finalize,
+    fund,
+    lockAndPublish,
+    netOf,
+    placeBet,
+    profilePda,
+    program,
+    settleClaim,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › User profiles", function () {
+    this.timeout(180000);
+
+    let slate: Slate;
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+    });
+
+    it("Keeps lifetime stats across bets and settlements", async () => {
+        const bettor = await fund();
+        const won = await createPool(slate, 100);
+        const lost = await createPool(slate, 200);
+
+        const winningPick = await placeBet(won, bettor, 100_000_000, true);
+        await placeBet(won, await fund(), 100_000_000, false);
+        const losingPick = await placeBet(lost, bettor, 50_000_000, true);
+        await placeBet(lost, await fund(), 50_000_000, false);
+
+        await lockAndPublish(won, 150);
+        await lockAndPublish(lost, 150);
+        await finalize(won);
+        await finalize(lost);
+        await settleClaim(won, winningPick, bettor);
+        await settleClaim(lost, losingPick, bettor);
+
+        const profile = await program.account.userProfile.fetch(profilePda(bettor.publicKey));
+        expect(profile.wallet.toBase58()).to.equal(bettor.publicKey.toBase58());
+        expect(profile.pickCount.toNumber()).to.equal(2);
+        expect(profile.totalWagered.toNumber()).to.equal(150_000_000);
+        expect(profile.totalFeesPaid.toNumber()).to.equal(150_000_000 - netOf(100_000_000) - netOf(50_000_000));
+        expect(profile.totalWon.toNumber()).to.equal(2 * netOf(100_000_000));
+        expect(profile.winCount).to.equal(1);
+        expect(profile.lossCount).to.equal(1);
+        expect(profile.currentStreak).to.equal(-1);
+        console.log("✅ Profile records 1 win, 1 loss");
+    });
+
+    it("Lets anyone open a profile for a wallet, but only at its own address", async () => {
+        const wallet = Keypair.generate().publicKey;
+        await program.methods
+            .initializeUserProfile(wallet)
+            .accountsPartial({
+                payer: admin.publicKey,
+                userProfile: profilePda(wallet),
+                systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+        const profile = await program.account.userProfile.fetch(profilePda(wallet));
+        expect(profile.wallet.toBase58()).to.equal(wallet.toBase58());
+        expect(profile.pickCount.toNumber()).to.equal(0);
+
+        // Settling into someone else's profile would credit the wrong record
+        const pool = await createPool(slate, 300);
+        const bettor = await fund();
+        const pick = await placeBet(pool, bettor, 100_000_000, true);
+        await placeBet(pool, await fund(), 100_000_000, false);
+        await lockAndPublish(pool, 350);
+        await finalize(pool);
+        await expectError(settleClaim(pool, pick, bettor, { profile: profilePda(wallet) }), "ConstraintSeeds");
+        console.log("✅ Sponsored profile opened for", wallet.toBase58());
+    });
+});
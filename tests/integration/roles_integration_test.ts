@@ -0,0 +1,81 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+    Pool,
+    STAT,
+    Slate,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    exists,
+    expectError,
+    fund,
+    grantRole,
+    program,
+    revokeRole,
+    roleGrantPda,
+    ROLES,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Roles", function () {
+    this.timeout(180000);
+
+    let slate: Slate;
+
+    const lockAs = (pool: Pool, authority: Keypair, roleGrant: PublicKey | null) =>
+        program.methods
+            .lockBetPool()
+            .accountsPartial({ betPool: pool.betPool, authority: authority.publicKey, roleGrant })
+            .signers([authority])
+            .rpc();
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+    });
+
+    it("Lets a granted desk create pools and a granted resolver lock them", async () => {
+        const desk = await fund();
+        const resolver = await fund(10_000_000);
+        const deskGrant = await grantRole("poolCreator", desk.publicKey);
+        const resolverGrant = await grantRole("resolver", resolver.publicKey);
+
+        const grant = await program.account.roleGrant.fetch(deskGrant);
+        expect(grant.grantee.toBase58()).to.equal(desk.publicKey.toBase58());
+        expect(grant.role).to.deep.equal({ poolCreator: {} });
+
+        const pool = await createPool(slate, 100, STAT, { authority: desk, roleGrant: deskGrant });
+        await lockAs(pool, resolver, resolverGrant);
+        expect((await program.account.betPool.fetch(pool.betPool)).status).to.deep.equal({ locked: {} });
+        console.log("✅ Desk", desk.publicKey.toBase58(), "created a pool");
+    });
+
+    it("Denies operations without the matching role, and after revocation", async () => {
+        const desk = await fund();
+        await expectError(createPool(slate, 200, STAT, { authority: desk, roleGrant: null }), "MissingRole");
+
+        const deskGrant = await grantRole("poolCreator", desk.publicKey);
+        const pool = await createPool(slate, 200, STAT, { authority: desk, roleGrant: deskGrant });
+        // A pool creator grant doesn't make the desk a resolver
+        await expectError(lockAs(pool, desk, deskGrant), "MissingRole");
+
+        // Only the admin hands out roles
+        await expectError(
+            program.methods
+                .grantRole({ treasurer: {} }, desk.publicKey)
+                .accountsPartial({
+                    admin: desk.publicKey,
+                    roleGrant: roleGrantPda(ROLES.treasurer, desk.publicKey),
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([desk])
+                .rpc(),
+            "Unauthorized"
+        );
+
+        await revokeRole(deskGrant);
+        expect(await exists(deskGrant)).to.equal(false);
+        await expectError(createPool(slate, 300, STAT, { authority: desk, roleGrant: null }), "MissingRole");
+        console.log("✅ Missing and revoked roles denied");
+    });
+});
@@ -0,0 +1,178 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+    BN,
+    SESSION_PLACE_BET,
+    SESSION_SETTLE_CLAIM,
+    Slate,
+    balancePda,
+    chainTime,
+    connection,
+    createPool,
+    createSlate,
+    deposit,
+    ensureProtocol,
+    exists,
+    expectError,
+    finalize,
+    fund,
+    lockAndPublish,
+    netOf,
+    pda,
+    placeBet,
+    program,
+    settleClaim,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Sessions", function () {
+    this.timeout(180000);
+
+    let slate: Slate;
+
+    const sessionPda = (owner: PublicKey, sessionKey: PublicKey) =>
+        pda(Buffer.from("session"), owner.toBuffer(), sessionKey.toBuffer());
+
+    const createSession = async (
+        owner: Keypair,
+        sessionKey: PublicKey,
+        spendCap: number,
+        allowedInstructions: number,
+        expiresIn = 3600
+    ) => {
+        const sessionToken = sessionPda(owner.publicKey, sessionKey);
+        await program.methods
+            .createSession(sessionKey, new BN(spendCap), new BN((await chainTime()) + expiresIn), allowedInstructions)
+            .accountsPartial({
+                owner: owner.publicKey,
+                sessionToken,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([owner])
+            .rpc();
+        return sessionToken;
+    };
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+    });
+
+    it("Enforces the session spend cap", async () => {
+        const pool = await createPool(slate, 400);
+        const owner = await fund();
+        const sessionKey = await fund(500_000_000);
+        const spendCap = 200_000_000;
+
+        const balance = balancePda(owner.publicKey);
+        await deposit(owner, 500_000_000);
+
+        const sessionToken = await createSession(owner, sessionKey.publicKey, spendCap, SESSION_PLACE_BET);
+        const viaSession = { authority: sessionKey, sessionToken };
+
+        // Session-signed bets can't pull from the owner's wallet
+        await expectError(placeBet(pool, owner.publicKey, 50_000_000, true, viaSession), "BalanceRequired");
+
+        await placeBet(pool, owner.publicKey, 150_000_000, true, { ...viaSession, balance });
+        const session = await program.account.sessionToken.fetch(sessionToken);
+        expect(session.spent.toNumber()).to.equal(150_000_000);
+
+        await expectError(
+            placeBet(pool, owner.publicKey, 60_000_000, true, { ...viaSession, balance }),
+            "SessionSpendCapExceeded"
+        );
+
+        // Exactly reaching the cap is still allowed
+        await placeBet(pool, owner.publicKey, 50_000_000, false, { ...viaSession, balance });
+        const spent = (await program.account.sessionToken.fetch(sessionToken)).spent.toNumber();
+        expect(spent).to.equal(spendCap);
+
+        // The owner signing directly is not bound by the session's cap
+        await placeBet(pool, owner, 100_000_000, true, { balance });
+        expect((await program.account.balance.fetch(balance)).amount.toNumber()).to.equal(200_000_000);
+        console.log("✅ Session capped at", spendCap);
+    });
+
+    it("Rejects malformed session parameters", async () => {
+        const owner = await fund();
+
+        await expectError(
+            createSession(owner, Keypair.generate().publicKey, 100_000_000, 0),
+            "InvalidSessionParams"
+        );
+        await expectError(
+            createSession(owner, Keypair.generate().publicKey, 100_000_000, 1 << 7),
+            "InvalidSessionParams"
+        );
+        await expectError(
+            createSession(owner, Keypair.generate().publicKey, 100_000_000, SESSION_PLACE_BET, -60),
+            "InvalidSessionParams"
+        );
+        console.log("✅ Empty mask, unknown bits and past expiry rejected");
+    });
+
+    it("Limits a session to its instruction mask", async () => {
+        const pool = await createPool(slate, 450);
+        const owner = await fund();
+        const opponent = await fund();
+        const betKey = await fund(100_000_000);
+        const settleKey = await fund(100_000_000);
+
+        const balance = balancePda(owner.publicKey);
+        await deposit(owner, 200_000_000);
+        const betSession = await createSession(owner, betKey.publicKey, 200_000_000, SESSION_PLACE_BET);
+        const settleSession = await createSession(owner, settleKey.publicKey, 0, SESSION_SETTLE_CLAIM);
+
+        // A settle-only session can't stake, not even within its (zero) cap
+        await expectError(
+            placeBet(pool, owner.publicKey, 50_000_000, true, { authority: settleKey, sessionToken: settleSession, balance }),
+            "SessionInstructionNotAllowed"
+        );
+        const pick = await placeBet(pool, owner.publicKey, 100_000_000, true, {
+            authority: betKey,
+            sessionToken: betSession,
+            balance,
+        });
+        await placeBet(pool, opponent, 100_000_000, false);
+
+        await lockAndPublish(pool, 500);
+        await finalize(pool);
+
+        await expectError(
+            settleClaim(pool, pick, owner.publicKey, { authority: betKey, sessionToken: betSession }),
+            "SessionInstructionNotAllowed"
+        );
+
+        const walletBefore = await connection.getBalance(owner.publicKey);
+        await settleClaim(pool, pick, owner.publicKey, { authority: settleKey, sessionToken: settleSession });
+        expect((await connection.getBalance(owner.publicKey)) - walletBefore).to.equal(2 * netOf(100_000_000));
+        console.log("✅ Claim settled by the settle-only session key");
+    });
+
+    it("Stops honouring a revoked session", async () => {
+        const pool = await createPool(slate, 550);
+        const owner = await fund();
+        const sessionKey = await fund(100_000_000);
+
+        const balance = balancePda(owner.publicKey);
+        await deposit(owner, 200_000_000);
+        const sessionToken = await createSession(owner, sessionKey.publicKey, 200_000_000, SESSION_PLACE_BET);
+        const viaSession = { authority: sessionKey, sessionToken, balance };
+
+        await placeBet(pool, owner.publicKey, 50_000_000, true, viaSession);
+
+        const ownerBefore = await connection.getBalance(owner.publicKey);
+        const rent = await connection.getBalance(sessionToken);
+        await program.methods
+            .revokeSession()
+            .accountsPartial({ owner: owner.publicKey, sessionToken })
+            .signers([owner])
+            .rpc();
+
+        expect(await exists(sessionToken)).to.equal(false);
+        // The provider pays the fee, so the owner gets back exactly the rent
+        expect((await connection.getBalance(owner.publicKey)) - ownerBefore).to.equal(rent);
+
+        await expectError(placeBet(pool, owner.publicKey, 50_000_000, false, viaSession), "AccountNotInitialized");
+        console.log("✅ Revoked session closed and refused");
+    });
+});
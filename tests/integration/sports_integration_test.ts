@@ -0,0 +1,73 @@
+import { expect } from "chai";
+import { SystemProgram } from "@solana/web3.js";
+import {
+    SPORT,
+    STAT,
+    Slate,
+    admin,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    expectError,
+    lockPool,
+    name,
+    pda,
+    program,
+    publishResult,
+    sportPda,
+    upsertStatType,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Sports and stat types", function () {
+    this.timeout(180000);
+
+    let slate: Slate;
+
+    const statRange = async (statName: string) => {
+        const sport = await program.account.sport.fetch(sportPda);
+        const statType = sport.statTypes
+            .slice(0, sport.statTypeCount)
+            .find((t: any) => Buffer.from(t.statName).equals(Buffer.from(name(statName))));
+        return statType && [statType.minValue, statType.maxValue];
+    };
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+    });
+
+    it("Registers stat types per sport and prices pools against them", async () => {
+        await upsertStatType("COMPLETIONS", 80);
+        expect(await statRange("COMPLETIONS")).to.deep.equal([0, 80]);
+        await createPool(slate, 25, "COMPLETIONS");
+
+        // Upserting an existing stat replaces its range in place
+        await upsertStatType("COMPLETIONS", 90);
+        expect(await statRange("COMPLETIONS")).to.deep.equal([0, 90]);
+        console.log("✅ COMPLETIONS registered for", SPORT);
+    });
+
+    it("Rejects non-canonical names, unknown stats and implausible values", async () => {
+        await expectError(
+            program.methods
+                .initializeSport(name("nfl"))
+                .accountsPartial({
+                    admin: admin.publicKey,
+                    sport: pda(Buffer.from("sport"), Buffer.from(name("nfl"))),
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc(),
+            "NonCanonicalName"
+        );
+        await expectError(upsertStatType("passing_yards", 1000), "NonCanonicalName");
+
+        await expectError(createPool(slate, 100, "TACKLES"), "InvalidStatType");
+        // Lines outside the registered range can never resolve sensibly
+        await expectError(createPool(slate, 5000), "InvalidStatLine");
+
+        const pool = await createPool(slate, 100);
+        await lockPool(pool);
+        await expectError(publishResult(pool, 5000), "ImplausibleStatValue");
+        console.log("✅", STAT, "values outside 0..=1000 rejected");
+    });
+});
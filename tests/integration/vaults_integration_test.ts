@@ -0,0 +1,67 @@
+import { expect } from "chai";
+import {
+    BN,
+    Slate,
+    admin,
+    createPool,
+    createSlate,
+    ensureProtocol,
+    executeCouncilAction,
+    expectError,
+    fund,
+    netOf,
+    placeBet,
+    program,
+    vaultExcess,
+    withdrawFees,
+} from "./helpers";
+
+describe("Fantasy Sports Full Contract Test › Typed vaults", function () {
+    this.timeout(180000);
+
+    let slate: Slate;
+
+    before(async () => {
+        await ensureProtocol();
+        slate = await createSlate();
+    });
+
+    it("Tracks deposits and withdrawals in the vault accounts", async () => {
+        const pool = await createPool(slate, 100);
+        await placeBet(pool, await fund(), 100_000_000, true);
+        await placeBet(pool, await fund(), 40_000_000, false);
+
+        const betVault = await program.account.betVault.fetch(pool.betVault);
+        expect(betVault.owner.toBase58()).to.equal(pool.betPool.toBase58());
+        expect(betVault.totalDeposited.toNumber()).to.equal(netOf(100_000_000) + netOf(40_000_000));
+        expect(await vaultExcess(pool.betVault)).to.equal(betVault.totalDeposited.toNumber());
+
+        const fees = 140_000_000 - netOf(100_000_000) - netOf(40_000_000);
+        expect((await program.account.feeVault.fetch(pool.feeVault)).totalDeposited.toNumber()).to.equal(fees);
+
+        await withdrawFees(pool, 1_000_000);
+        const feeVault = await program.account.feeVault.fetch(pool.feeVault);
+        expect(feeVault.totalWithdrawn.toNumber()).to.equal(1_000_000);
+        expect(await vaultExcess(pool.feeVault)).to.equal(fees - 1_000_000);
+        console.log("✅ Vault ledgers match their lamports");
+    });
+
+    it("Never lets a withdrawal dip into rent or cross pools", async () => {
+        const pool = await createPool(slate, 200);
+        const other = await createPool(slate, 300);
+        await placeBet(pool, await fund(), 100_000_000, true);
+
+        const excess = await vaultExcess(pool.feeVault);
+        await expectError(withdrawFees(pool, excess + 1), "VaultOverdrawn");
+
+        await expectError(
+            executeCouncilAction(
+                { withdrawFees: { betPool: pool.betPool, amount: new BN(1) } },
+                { betPool: pool.betPool, feeVault: other.feeVault, treasury: admin.publicKey }
+            ),
+            "InvalidFeeVault"
+        );
+        expect(await vaultExcess(pool.feeVault)).to.equal(excess);
+        console.log("✅ Over-withdrawal and foreign vault rejected");
+    });
+});